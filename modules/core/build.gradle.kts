@@ -10,10 +10,33 @@ plugins {
 
 intellij {
     version.set(properties("platformVersion"))
+    // The config form edits yaml and toml files through their PSI, and the schema of toml configs is enabled through the TOML plugin.
+    // Both are registered only if the plugins are installed.
+    plugins.set(listOf("org.jetbrains.plugins.yaml", "org.toml.lang"))
 }
 
 dependencies {
     implementation("com.google.code.gson:gson:2.10.1")
     implementation("com.github.zafarkhaja:java-semver:0.9.0")
     implementation("org.jetbrains.kotlinx:kotlinx-collections-immutable:0.3.5")
+    // Jackson core and databind are bundled with the IDE, bundling them again could clash with the classes of the platform.
+    // The dataformats match the Jackson version of the oldest supported platform (2.16.0 in 2024.1).
+    implementation("com.fasterxml.jackson.dataformat:jackson-dataformat-yaml:2.16.0") {
+        exclude(group = "com.fasterxml.jackson.core")
+    }
+    implementation("com.fasterxml.jackson.dataformat:jackson-dataformat-toml:2.16.0") {
+        exclude(group = "com.fasterxml.jackson.core")
+    }
+    testImplementation("org.junit.jupiter:junit-jupiter-api:5.9.3")
+    // The platform test cases are JUnit 3 tests, run through the vintage engine.
+    testImplementation("junit:junit:4.13.2")
+    testRuntimeOnly("org.junit.jupiter:junit-jupiter-engine:5.9.2")
+    testRuntimeOnly("org.junit.vintage:junit-vintage-engine:5.9.2")
+    testRuntimeOnly("org.junit.platform:junit-platform-launcher:1.9.3")
+}
+
+tasks {
+    test {
+        useJUnitPlatform()
+    }
 }
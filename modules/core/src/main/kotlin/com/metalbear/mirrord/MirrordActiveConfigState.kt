@@ -0,0 +1,91 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.configurations.RunConfiguration
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.openapi.components.PersistentStateComponent
+import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.State
+import com.intellij.openapi.components.Storage
+import com.intellij.openapi.components.StoragePathMacros
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.vfs.LocalFileSystem
+import com.intellij.openapi.vfs.VirtualFile
+import com.intellij.openapi.vfs.VirtualFileManager
+
+/**
+ * The active config of the project, stored in the workspace file, since the selection belongs to the user and not to the shared project.
+ * The config is stored as a URL, a config that no longer exists is treated as not set.
+ *
+ * A config is bound to a single run configuration by setting [MirrordRunConfigurationOptions.configFile],
+ * so that it's stored with the run configuration and follows its renames.
+ */
+@Service(Service.Level.PROJECT)
+@State(name = "MirrordActiveConfig", storages = [Storage(StoragePathMacros.WORKSPACE_FILE)])
+class MirrordActiveConfigState(private val project: Project) : PersistentStateComponent<MirrordActiveConfigState.Configs> {
+    class Configs {
+        var activeConfigUrl: String? = null
+    }
+
+    private var configs = Configs()
+
+    @Synchronized
+    override fun getState(): Configs = configs
+
+    @Synchronized
+    override fun loadState(state: Configs) {
+        configs = state
+    }
+
+    /**
+     * Config used by the run configurations that have no config set.
+     */
+    var activeConfig: VirtualFile?
+        get() = synchronized(this) { configs.activeConfigUrl }?.let { url ->
+            VirtualFileManager.getInstance().findFileByUrl(url)?.takeIf { it.isValid && !it.isDirectory }
+        }
+        set(value) {
+            synchronized(this) { configs.activeConfigUrl = value?.url }
+        }
+
+    /**
+     * @return the config set in the mirrord tab of the run configuration, null if there is none, or it does not exist
+     */
+    fun boundConfig(configuration: RunConfiguration): VirtualFile? {
+        val base = configuration as? RunConfigurationBase<*> ?: return null
+        val path = MirrordRunConfigurationOptions.get(base).configFile ?: return null
+        val expanded = try {
+            MirrordPathMacros.expandConfigPath(project, base, path)
+        } catch (e: MirrordError) {
+            return null
+        }
+        return LocalFileSystem.getInstance().findFileByPath(expanded)?.takeIf { it.isValid && !it.isDirectory }
+    }
+
+    /**
+     * Sets the config in the mirrord tab of the run configuration, it's used instead of the active config.
+     * The path is stored with macros, since the run configuration may be shared.
+     *
+     * @param config null to remove the config
+     */
+    fun bind(configuration: RunConfigurationBase<*>, config: VirtualFile?) {
+        val options = MirrordRunConfigurationOptions.get(configuration)
+        val path = config?.let { MirrordPathMacros.collapseConfigPath(project, it.path) }
+        MirrordRunConfigurationOptions.set(configuration, options.copy(configFile = path))
+    }
+
+    /**
+     * Points the config in the mirrord tab of the run configuration to the new location of the moved config,
+     * keeping the macros the user set in the path.
+     *
+     * @param config null if the config was removed
+     */
+    fun followMove(configuration: RunConfigurationBase<*>, config: VirtualFile?) {
+        val options = MirrordRunConfigurationOptions.get(configuration)
+        val path = config?.let { moved ->
+            options.configFile
+                ?.let { MirrordPathMacros.rebaseConfigPath(project, configuration, it, moved.path) }
+                ?: MirrordPathMacros.collapseConfigPath(project, moved.path)
+        }
+        MirrordRunConfigurationOptions.set(configuration, options.copy(configFile = path))
+    }
+}
@@ -1,71 +1,111 @@
 package com.metalbear.mirrord
 
+import com.intellij.execution.configurations.RunConfigurationBase
 import com.intellij.notification.NotificationType
 import com.intellij.openapi.application.ReadAction
+import com.intellij.openapi.components.service
 import com.intellij.openapi.vfs.AsyncFileListener
+import com.intellij.openapi.vfs.VfsUtilCore
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.openapi.vfs.VirtualFileManager
 import com.intellij.openapi.vfs.newvfs.events.VFileDeleteEvent
 import com.intellij.openapi.vfs.newvfs.events.VFileEvent
 import com.intellij.openapi.vfs.newvfs.events.VFileMoveEvent
+import com.intellij.openapi.vfs.newvfs.events.VFilePropertyChangeEvent
 
 class MirrordActiveConfigWatch(private val service: MirrordProjectService) : AsyncFileListener {
     /**
+     * @param configuration run configuration the config is bound to, null for the active config
      * @param url null means that the file has been removed
      */
-    class ConfigMovedTo(val url: String?)
+    class ConfigMovedTo(val configuration: RunConfigurationBase<*>?, val url: String?)
 
     /**
-     * Searches for deletions and renames of the active config file (or any of its parent directories).
+     * Searches for deletions, moves and renames of the active config file and the configs bound to run configurations
+     * (or any of their parent directories).
      * When the change has been applied to the VFS, a warning notification is displayed to the user
-     * and the active config file reference is updated.
+     * and the config reference is updated.
      */
     override fun prepareChange(events: MutableList<out VFileEvent>): AsyncFileListener.ChangeApplier? {
-        val result: ConfigMovedTo? = service
-            .activeConfig
-            ?.url
-            ?.let { activeUrl ->
-                events
-                    .filter { it.file?.let { file -> activeUrl.startsWith(file.url) } ?: false }
-                    .firstNotNullOfOrNull {
-                        when (it) {
-                            is VFileDeleteEvent -> ConfigMovedTo(null)
-                            is VFileMoveEvent -> ConfigMovedTo(activeUrl.replace(it.oldParent.url, it.newParent.url))
-                            else -> null
-                        }
-                    }
+        // Most events are content changes, which can't move a config.
+        val relevant = events.filter { it is VFileDeleteEvent || it is VFileMoveEvent || (it is VFilePropertyChangeEvent && it.isRename) }
+        if (relevant.isEmpty()) {
+            return null
+        }
+
+        val activeConfig = service.activeConfig?.let { ConfigMovedTo(null, it.url) }
+        // The expanded paths are cached, see [MirrordConfigReferences].
+        val boundConfigs = service
+            .project
+            .service<MirrordConfigReferences>()
+            .references()
+            .mapNotNull { reference ->
+                val configuration = reference.configuration as? RunConfigurationBase<*> ?: return@mapNotNull null
+                reference.configFile?.let { ConfigMovedTo(configuration, VfsUtilCore.pathToUrl(it)) }
             }
 
-        return result?.let {
-            object : AsyncFileListener.ChangeApplier {
-                override fun afterVfsChange() {
-                    val newActiveConfig = it.url?.let { url ->
-                        ReadAction.compute<VirtualFile, Exception> {
-                            VirtualFileManager.getInstance().findFileByUrl(url)
-                        }
-                    }
+        val results = (listOfNotNull(activeConfig) + boundConfigs).mapNotNull { config ->
+            config.url?.let { movedTo(config.configuration, it, relevant) }
+        }
+        if (results.isEmpty()) {
+            return null
+        }
 
-                    service.activeConfig = newActiveConfig
+        return object : AsyncFileListener.ChangeApplier {
+            override fun afterVfsChange() {
+                results.forEach { apply(it) }
+            }
+        }
+    }
 
-                    val notification = if (newActiveConfig == null) {
-                        service.notifier.notification(
-                            "mirrord active config has been removed",
-                            NotificationType.WARNING
-                        )
-                            .withDontShowAgain(MirrordSettingsState.NotificationId.ACTIVE_CONFIG_REMOVED)
-                    } else {
-                        service.notifier.notification(
-                            "mirrord active config has been moved to ${newActiveConfig.presentableUrl}",
-                            NotificationType.WARNING
-                        )
-                            .withDontShowAgain(MirrordSettingsState.NotificationId.ACTIVE_CONFIG_MOVED)
+    /**
+     * @return null if the config is not affected by the events
+     */
+    private fun movedTo(configuration: RunConfigurationBase<*>?, url: String, events: List<VFileEvent>): ConfigMovedTo? {
+        return events
+            .filter { it.file?.let { file -> url == file.url || url.startsWith(file.url + "/") } ?: false }
+            .firstNotNullOfOrNull {
+                when {
+                    it is VFileDeleteEvent -> ConfigMovedTo(configuration, null)
+                    it is VFileMoveEvent -> ConfigMovedTo(configuration, it.newParent.url + url.removePrefix(it.oldParent.url))
+                    it is VFilePropertyChangeEvent && it.isRename -> it.file.parent?.let { parent ->
+                        ConfigMovedTo(configuration, "${parent.url}/${it.newValue}" + url.removePrefix(it.file.url))
                     }
-
-                    notification.fire()
-
-                    return
+                    else -> null
                 }
             }
+    }
+
+    private fun apply(movedTo: ConfigMovedTo) {
+        val newConfig = movedTo.url?.let { url ->
+            ReadAction.compute<VirtualFile, Exception> {
+                VirtualFileManager.getInstance().findFileByUrl(url)
+            }
         }
+
+        val configuration = movedTo.configuration
+        val configName = if (configuration == null) {
+            service.activeConfig = newConfig
+            "mirrord active config"
+        } else {
+            service.activeConfigs.followMove(configuration, newConfig)
+            "mirrord config of \"${configuration.name}\""
+        }
+
+        val notification = if (newConfig == null) {
+            service.notifier.notification(
+                "$configName has been removed",
+                NotificationType.WARNING
+            )
+                .withDontShowAgain(MirrordSettingsState.NotificationId.ACTIVE_CONFIG_REMOVED)
+        } else {
+            service.notifier.notification(
+                "$configName has been moved to ${newConfig.presentableUrl}",
+                NotificationType.WARNING
+            )
+                .withDontShowAgain(MirrordSettingsState.NotificationId.ACTIVE_CONFIG_MOVED)
+        }
+
+        notification.fire()
     }
 }
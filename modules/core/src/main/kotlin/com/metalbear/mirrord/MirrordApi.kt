@@ -16,6 +16,7 @@ import com.intellij.openapi.progress.ProgressIndicator
 import com.intellij.openapi.progress.ProgressManager
 import com.intellij.openapi.progress.Task
 import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.text.StringUtil
 import java.util.concurrent.*
 
 const val GITHUB_URL = "https://github.com/metalbear-co/mirrord"
@@ -39,21 +40,6 @@ enum class NotificationLevel {
     Info, Warning
 }
 
-/**
- * Rust enum equivalent to the `IdeAction`.
- *
- * Converted from a `JsonObject` from `IdeMessage`.
- */
-sealed class IdeAction {
-    /**
-     * A link action that appears in the notification, such as "Get help".
-     *
-     * @param label The text of the link: "Get help".
-     * @param link The Url.
-     */
-    data class Link(val label: String, val link: String) : IdeAction()
-}
-
 /**
  * Message we get from mirrord in json format, when `MessageType` is `IdeMessage`.
  *
@@ -72,20 +58,23 @@ data class IdeMessage(val id: String, val level: NotificationLevel, val text: St
      * Handles the `IdeMessage` that we received from mirrord.
      *
      * @param service Used to build the notification.
+     * @param session Session of the mirrord invocation that sent the message, the actions are performed for its run.
      */
-    fun handleIdeMessage(service: MirrordProjectService) {
+    fun handleIdeMessage(service: MirrordProjectService, session: MirrordSession) {
+        val (unknown, known) = this.actions
+            .map { IdeActionRegistry.parse(it) }
+            .partition { it is IdeAction.Unknown }
+
+        // Actions that this version of the plugin can't handle are displayed as plain text.
+        val content = (listOf(text) + unknown.map { StringUtil.escapeXmlEntities(it.label) }).joinToString("<br>")
+
         val notification = when (level) {
-            NotificationLevel.Info -> service.notifier.notification(text, NotificationType.INFORMATION)
-            NotificationLevel.Warning -> service.notifier.notification(text, NotificationType.WARNING)
+            NotificationLevel.Info -> service.notifier.notification(content, NotificationType.INFORMATION)
+            NotificationLevel.Warning -> service.notifier.notification(content, NotificationType.WARNING)
         }
 
-        this.actions.forEach {
-            if (it["kind"].asString == "Link") {
-                val action = Gson().fromJson(it, IdeAction.Link::class.java)
-                var link = action.link.replace("utm_medium=plugin", "utm_medium=intellij")
-                link = link.replace("utm_medium=cli", "utm_medium=intellij")
-                notification.withLink(action.label, link)
-            }
+        known.forEach { action ->
+            notification.withAction(action.label) { _, _ -> IdeActionRegistry.perform(action, service, session) }
         }
 
         notification.fire()
@@ -120,6 +109,17 @@ private class SafeParser {
     }
 }
 
+/**
+ * Overrides `target.namespace` from the mirrord config.
+ */
+private const val TARGET_NAMESPACE_ENV_NAME = "MIRRORD_TARGET_NAMESPACE"
+
+/**
+ * Name of the top level task reported by `mirrord ext`.
+ * When this task finishes, its message contains the `MirrordExecution`.
+ */
+private const val LAUNCH_TASK_NAME = "mirrord preparing to launch"
+
 /**
  * How many times mirrord can be run before asking for marketplace review.
  */
@@ -144,8 +144,8 @@ private const val MIRRORD_FOR_TEAMS_INVITE_EVERY = 30
  * Interact with mirrord CLI using this API.
  */
 class MirrordApi(private val service: MirrordProjectService, private val projectEnvVars: Map<String, String>?) {
-    private class MirrordLsTask(cli: String, projectEnvVars: Map<String, String>?) : MirrordCliTask<List<String>>(cli, "ls", null, projectEnvVars) {
-        override fun compute(project: Project, process: Process, setText: (String) -> Unit): List<String> {
+    private class MirrordLsTask(cli: String, projectEnvVars: Map<String, String>?) : MirrordCliTask<MirrordTargetListing>(cli, "ls", null, projectEnvVars) {
+        override fun compute(project: Project, process: Process, setText: (String) -> Unit): MirrordTargetListing {
             setText("mirrord is listing targets...")
 
             process.waitFor()
@@ -157,33 +157,48 @@ class MirrordApi(private val service: MirrordProjectService, private val project
             val data = process.inputStream.bufferedReader().readText()
             MirrordLogger.logger.debug("parsing mirrord ls output: $data")
 
-            val pods = SafeParser().parse(data, Array<String>::class.java).toMutableList()
-
-            if (pods.isEmpty()) {
-                project.service<MirrordProjectService>().notifier.notifySimple("No mirrord target available in the configured namespace. You can run targetless, or set a different target namespace or kubeconfig in the mirrord configuration file.", NotificationType.INFORMATION)
+            val parser = SafeParser()
+            return if (data.trimStart().startsWith("[")) {
+                // Older versions of the mirrord binary output only a list of paths.
+                val paths = parser.parse(data, Array<String>::class.java)
+                MirrordTargetListing(MirrordTarget.fromFound(paths.map { FoundTarget(it, true) }, null), null, emptyList())
+            } else {
+                val found = parser.parse(data, FoundTargets::class.java)
+                MirrordTargetListing(
+                    MirrordTarget.fromFound(found.targets.orEmpty(), found.currentNamespace),
+                    found.currentNamespace,
+                    found.namespaces.orEmpty()
+                )
             }
-
-            return pods
         }
     }
 
     /**
      * Runs `mirrord ls` to get the list of available targets.
-     * Displays a modal progress dialog.
+     * Does not display any progress UI, meant to be called from a background thread.
      *
-     * @return list of pods
+     * @param namespace overrides the target namespace from the config, null to use the config
+     * @param kubeContext overrides the kube context from the config, null to use the config
+     * @param indicator used to cancel the listing
+     * @return available targets
      */
-    fun listPods(cli: String, configFile: String?, wslDistribution: WSLDistribution?): List<String> {
+    fun listPods(cli: String, configFile: String?, wslDistribution: WSLDistribution?, namespace: String?, kubeContext: String?, indicator: ProgressIndicator): MirrordTargetListing {
         val task = MirrordLsTask(cli, projectEnvVars).apply {
             this.configFile = configFile
             this.wslDistribution = wslDistribution
+            this.namespace = namespace
+            this.kubeContext = kubeContext
             this.output = "json"
+            this.extraEnv["MIRRORD_LS_RICH_OUTPUT"] = "true"
         }
 
-        return task.run(service.project)
+        return task.run(service.project, indicator)
     }
 
-    private class MirrordExtTask(cli: String, projectEnvVars: Map<String, String>?) : MirrordCliTask<MirrordExecution>(cli, "ext", null, projectEnvVars) {
+    /**
+     * Runs `mirrord ext`, reports the progress of the tasks to the given [session].
+     */
+    private class MirrordExtTask(cli: String, projectEnvVars: Map<String, String>?, private val session: MirrordSession) : MirrordCliTask<MirrordExecution>(cli, "ext", null, projectEnvVars) {
         override fun compute(project: Project, process: Process, setText: (String) -> Unit): MirrordExecution {
             val parser = SafeParser()
             val bufferedReader = process.inputStream.reader().buffered()
@@ -193,8 +208,19 @@ class MirrordApi(private val service: MirrordProjectService, private val project
             setText("mirrord is starting...")
             for (line in bufferedReader.lines()) {
                 val message = parser.parse(line, Message::class.java)
+
+                when (message.type) {
+                    MessageType.NewTask -> session.startTask(message.name, message.parent)
+                    MessageType.FinishedTask -> {
+                        // The final message carries the execution info (including the environment), don't display it.
+                        val text = message.message.takeIf { message.name != LAUNCH_TASK_NAME }?.toString()
+                        session.finishTask(message.name, message.success ?: false, text)
+                    }
+                    else -> {}
+                }
+
                 when {
-                    message.name == "mirrord preparing to launch" && message.type == MessageType.FinishedTask -> {
+                    message.name == LAUNCH_TASK_NAME && message.type == MessageType.FinishedTask -> {
                         val success = message.success
                             ?: throw MirrordError("invalid message received from the mirrord binary")
                         if (success) {
@@ -219,7 +245,7 @@ class MirrordApi(private val service: MirrordProjectService, private val project
                         message.message?.run {
                             val ideMessage = Gson().fromJson(Gson().toJsonTree(this), IdeMessage::class.java)
                             val service = project.service<MirrordProjectService>()
-                            ideMessage?.handleIdeMessage(service)
+                            ideMessage?.handleIdeMessage(service, session)
                         }
                     }
 
@@ -282,34 +308,146 @@ class MirrordApi(private val service: MirrordProjectService, private val project
         return verifyConfigTask.run(service.project)
     }
 
+    /**
+     * Executes the `mirrord verify-config [path]` task without any progress UI.
+     *
+     * @param indicator used to cancel the task
+     * @return String containing a json with either a success + warnings, or the verified config errors.
+     */
+    fun verifyConfig(cli: String, configFilePath: String, indicator: ProgressIndicator): String {
+        return MirrordVerifyConfigTask(cli, configFilePath, projectEnvVars).run(service.project, indicator)
+    }
+
     /**
      * Runs `mirrord ext` command to get the environment.
      * Displays a modal progress dialog.
      *
+     * @param namespace overrides the target namespace from the config, null to use the config
+     * @param session session that displays the progress, finished when this function returns
      * @return environment for the user's application
      */
-    fun exec(cli: String, target: String?, configFile: String?, executable: String?, wslDistribution: WSLDistribution?): MirrordExecution {
+    fun exec(
+        cli: String,
+        target: String?,
+        namespace: String?,
+        configFile: String?,
+        executable: String?,
+        wslDistribution: WSLDistribution?,
+        session: MirrordSession
+    ): MirrordExecution {
         bumpRunCounter()
 
-        val task = MirrordExtTask(cli, projectEnvVars).apply {
+        val task = MirrordExtTask(cli, projectEnvVars, session).apply {
+            this.extraEnv.putAll(service.takeNextRunEnv(session.run?.runProfile))
             this.target = target
+            this.namespace = namespace
+            this.kubeContext = service.kubeContext
             this.configFile = configFile
             this.executable = executable
             this.wslDistribution = wslDistribution
         }
 
-        val result = task.run(service.project)
+        val result = try {
+            task.run(service.project)
+        } catch (e: Throwable) {
+            session.finish(false)
+            throw e
+        }
+        session.finish(true)
         service.notifier.notifySimple("mirrord starting...", NotificationType.INFORMATION)
 
         result.usesOperator?.let { usesOperator ->
             if (usesOperator) {
                 MirrordSettingsState.instance.mirrordState.operatorUsed = true
+                service.operator.refresh()
             }
         }
 
         return result
     }
 
+    /**
+     * Builds the `mirrord port-forward` command line, the process is started by [MirrordPortForwardManager].
+     * The json progress is kept, it is used to detect when the forwarding is ready.
+     *
+     * @param target null to forward through the target from the config, or targetless
+     * @param mapping `local_port:remote_host:remote_port`
+     */
+    fun portForwardCommandLine(cli: String, target: String?, configFile: String?, mapping: String): GeneralCommandLine {
+        return MirrordCommandLineBuilder(cli, "port-forward", listOf("-L", mapping), projectEnvVars).apply {
+            this.target = target
+            this.kubeContext = service.kubeContext
+            this.configFile = configFile
+        }.prepareCommandLine(service.project)
+    }
+
+    /**
+     * Runs `mirrord operator status` to get the status of the operator, including the active sessions.
+     */
+    private class MirrordOperatorStatusTask(cli: String, projectEnvVars: Map<String, String>?) : MirrordCliTask<MirrordOperatorStatus>(cli, "operator status", null, projectEnvVars) {
+        override fun compute(project: Project, process: Process, setText: (String) -> Unit): MirrordOperatorStatus {
+            setText("mirrord is fetching the operator status...")
+
+            process.waitFor()
+            if (process.exitValue() != 0) {
+                val processStdError = process.errorStream.bufferedReader().readText()
+                throw MirrordError.fromStdErr(processStdError)
+            }
+
+            val data = process.inputStream.bufferedReader().readText()
+            MirrordLogger.logger.debug("parsing mirrord operator status output: $data")
+            return SafeParser().parse(data, MirrordOperatorStatus::class.java)
+        }
+    }
+
+    /**
+     * Runs `mirrord operator session <args>`, e.g. `kill --id <id>` or `kill-all`.
+     */
+    private class MirrordOperatorSessionTask(cli: String, args: List<String>, projectEnvVars: Map<String, String>?) : MirrordCliTask<Unit>(cli, "operator session", args, projectEnvVars) {
+        override fun compute(project: Project, process: Process, setText: (String) -> Unit) {
+            setText("mirrord is managing the operator sessions...")
+
+            process.waitFor()
+            if (process.exitValue() != 0) {
+                val processStdError = process.errorStream.bufferedReader().readText()
+                throw MirrordError.fromStdErr(processStdError)
+            }
+        }
+    }
+
+    /**
+     * Runs `mirrord operator status`.
+     * Does not display any progress UI, meant to be called from a background thread.
+     *
+     * @param indicator used to cancel the task
+     */
+    fun operatorStatus(cli: String, configFile: String?, indicator: ProgressIndicator): MirrordOperatorStatus {
+        val task = MirrordOperatorStatusTask(cli, projectEnvVars).apply {
+            this.kubeContext = service.kubeContext
+            this.configFile = configFile
+            this.output = "json"
+        }
+
+        return task.run(service.project, indicator)
+    }
+
+    /**
+     * Kills an operator session, or all sessions the user is allowed to kill.
+     * Does not display any progress UI, meant to be called from a background thread.
+     *
+     * @param sessionId null to kill all sessions
+     * @param indicator used to cancel the task
+     */
+    fun killOperatorSessions(cli: String, configFile: String?, sessionId: String?, indicator: ProgressIndicator) {
+        val args = sessionId?.let { listOf("kill", "--id", it) } ?: listOf("kill-all")
+        val task = MirrordOperatorSessionTask(cli, args, projectEnvVars).apply {
+            this.kubeContext = service.kubeContext
+            this.configFile = configFile
+        }
+
+        task.run(service.project, indicator)
+    }
+
     /**
      * Increments the mirrord run counter.
      * Can display some notifications (asking for feedback, discord invite, mirrord for Teams invite).
@@ -338,32 +476,50 @@ class MirrordApi(private val service: MirrordProjectService, private val project
 }
 
 /**
- * A mirrord CLI invocation.
+ * Builds the command line of a mirrord CLI invocation.
+ * Used on its own for long-running invocations, such as `mirrord port-forward`, where the process is managed by the caller.
  *
+ * @param command: The mirrord command, may include a subcommand separated with a space (e.g. `operator status`).
  * @param args: An extra list of arguments (used by `verify-config`).
  */
-private abstract class MirrordCliTask<T>(private val cli: String, private val command: String, private val args: List<String>?, private val projectEnvVars: Map<String, String>?) {
+private open class MirrordCommandLineBuilder(private val cli: String, private val command: String, private val args: List<String>?, private val projectEnvVars: Map<String, String>?) {
     var target: String? = null
+    var namespace: String? = null
+    var kubeContext: String? = null
     var configFile: String? = null
     var executable: String? = null
     var wslDistribution: WSLDistribution? = null
     var output: String? = null
 
+    /**
+     * Environment variables set for this invocation only, on top of the run configuration environment.
+     */
+    val extraEnv: MutableMap<String, String> = HashMap()
+
     /**
      * Returns command line for execution.
      */
-    private fun prepareCommandLine(project: Project): GeneralCommandLine {
-        return GeneralCommandLine(cli, command).apply {
+    fun prepareCommandLine(project: Project): GeneralCommandLine {
+        return GeneralCommandLine(listOf(cli) + command.split(' ')).apply {
             // Merge our `environment` vars with what's set in the current launch run configuration.
             if (projectEnvVars != null) {
                 environment.putAll(projectEnvVars)
             }
+            environment.putAll(extraEnv)
 
             target?.let {
                 addParameter("-t")
                 addParameter(it)
             }
 
+            namespace?.let {
+                environment[TARGET_NAMESPACE_ENV_NAME] = it
+            }
+
+            kubeContext?.let {
+                environment[KUBE_CONTEXT_ENV_NAME] = it
+            }
+
             configFile?.let {
                 val formattedPath = wslDistribution?.getWslPath(it) ?: it
                 addParameter("-f")
@@ -393,7 +549,13 @@ private abstract class MirrordCliTask<T>(private val cli: String, private val co
             environment["MIRRORD_PROGRESS_SUPPORT_IDE"] = "true"
         }
     }
+}
 
+/**
+ * A mirrord CLI invocation whose output is processed by the plugin.
+ */
+private abstract class MirrordCliTask<T>(cli: String, command: String, args: List<String>?, projectEnvVars: Map<String, String>?) :
+    MirrordCommandLineBuilder(cli, command, args, projectEnvVars) {
     /**
      * Processes the output of the mirrord process. If the user cancels the computation, process is destroyed.
      * @param setText used to present info about the computation state to the user
@@ -437,6 +599,46 @@ private abstract class MirrordCliTask<T>(private val cli: String, private val co
         }
     }
 
+    /**
+     * Attaches the config file to the errors thrown by [body], so that the config can be included in the issue report.
+     */
+    private fun <R> withConfigContext(body: () -> R): R {
+        return try {
+            body()
+        } catch (e: MirrordError) {
+            if (e.configPath == null) {
+                e.configPath = configFile
+            }
+            throw e
+        }
+    }
+
+    private fun startProcess(commandLine: GeneralCommandLine): Process {
+        MirrordLogger.logger.info("running mirrord task with following command line: ${commandLine.commandLineString}")
+        return commandLine.toProcessBuilder().redirectOutput(ProcessBuilder.Redirect.PIPE).redirectError(ProcessBuilder.Redirect.PIPE).start()
+    }
+
+    /**
+     * Computes the result of this invocation on the current thread, without any progress UI.
+     * The computation is canceled together with the given [indicator].
+     *
+     * @throws ProcessCanceledException if the indicator was canceled
+     */
+    fun run(project: Project, indicator: ProgressIndicator): T {
+        val commandLine = prepareCommandLine(project)
+        val process = startProcess(commandLine)
+
+        return try {
+            withConfigContext { computeWithResponsiveCancel(project, process, IndicatorProgressChecker(indicator)) }
+        } catch (e: Throwable) {
+            process.destroy()
+            if (e !is ProcessCanceledException) {
+                MirrordLogger.logger.warn("mirrord task `${commandLine.commandLineString}` failed", e)
+            }
+            throw e
+        }
+    }
+
     /**
      * Computes the result of this invocation with a progress UI:
      * * If called from the event dispatch thread, displays a modal dialog
@@ -447,10 +649,12 @@ private abstract class MirrordCliTask<T>(private val cli: String, private val co
      */
     fun run(project: Project): T {
         val commandLine = prepareCommandLine(project)
-        MirrordLogger.logger.info("running mirrord task with following command line: ${commandLine.commandLineString}")
+        val process = startProcess(commandLine)
 
-        val process = commandLine.toProcessBuilder().redirectOutput(ProcessBuilder.Redirect.PIPE).redirectError(ProcessBuilder.Redirect.PIPE).start()
+        return withConfigContext { runWithProgress(project, commandLine, process) }
+    }
 
+    private fun runWithProgress(project: Project, commandLine: GeneralCommandLine, process: Process): T {
         return if (ApplicationManager.getApplication().isDispatchThread) {
             // Modal dialog with progress is very visible and can be canceled by the user,
             // so we don't use any timeout here.
@@ -3,6 +3,7 @@ package com.metalbear.mirrord
 import com.github.zafarkhaja.semver.Version
 import com.intellij.execution.wsl.WSLDistribution
 import com.intellij.notification.NotificationType
+import com.intellij.openapi.Disposable
 import com.intellij.openapi.components.Service
 import com.intellij.openapi.components.service
 import com.intellij.openapi.progress.ProgressIndicator
@@ -11,6 +12,7 @@ import com.intellij.openapi.project.DumbAware
 import com.intellij.openapi.project.Project
 import com.intellij.openapi.startup.StartupActivity
 import com.intellij.openapi.util.SystemInfo
+import com.intellij.util.EventDispatcher
 import com.intellij.util.system.CpuArch
 import java.net.URI
 import java.net.URLEncoder
@@ -34,6 +36,34 @@ private const val DOWNLOAD_ENDPOINT = "https://github.com/metalbear-co/mirrord/r
  */
 @Service(Service.Level.APP)
 class MirrordBinaryManager {
+    fun interface Listener : EventListener {
+        /**
+         * Called from an arbitrary thread when a binary with a different version than before is used.
+         */
+        fun usedVersionChanged(version: String)
+    }
+
+    private val dispatcher = EventDispatcher.create(Listener::class.java)
+
+    /**
+     * Version of the binary returned from the last [getBinary] call, null if not known.
+     */
+    @Volatile
+    var usedVersion: String? = null
+        private set
+
+    fun addListener(listener: Listener, parentDisposable: Disposable) {
+        dispatcher.addListener(listener, parentDisposable)
+    }
+
+    private fun use(binary: MirrordBinary): String {
+        if (usedVersion != binary.version) {
+            usedVersion = binary.version
+            dispatcher.multicaster.usedVersionChanged(binary.version)
+        }
+        return binary.command
+    }
+
     @Volatile
     private var latestSupportedVersion: String? = null
     private var downloadVersion: String? = null
@@ -329,6 +359,30 @@ class MirrordBinaryManager {
         return findBinaryInPath(requiredVersion, wslDistribution) ?: findBinaryInStorage(requiredVersion, wslDistribution)
     }
 
+    /**
+     * Finds a local installation of the mirrord binary, preferably in the latest supported version.
+     * Unlike [getBinary], does not schedule a binary update and does not notify the user.
+     *
+     * @return the path to the binary, null if no local binary was found
+     */
+    fun findLocalBinary(wslDistribution: WSLDistribution?): String? {
+        return findLocalMirrordBinary(wslDistribution)?.command
+    }
+
+    /**
+     * Like [findLocalBinary], but returns the version of the binary.
+     *
+     * @return the version of the binary, null if no local binary was found
+     */
+    fun findLocalBinaryVersion(wslDistribution: WSLDistribution?): String? {
+        return findLocalMirrordBinary(wslDistribution)?.version
+    }
+
+    private fun findLocalMirrordBinary(wslDistribution: WSLDistribution?): MirrordBinary? {
+        return latestSupportedVersion?.let { getLocalBinary(it, wslDistribution) }
+            ?: getLocalBinary(null, wslDistribution)
+    }
+
     /**
      * Finds a local installation of the mirrord binary.
      * Schedules a binary update task to be executed in the background.
@@ -340,10 +394,14 @@ class MirrordBinaryManager {
         UpdateTask(project, product, wslDistribution, true).queue()
 
         latestSupportedVersion?.let { version ->
-            getLocalBinary(version, wslDistribution)?.let { return it.command }
+            getLocalBinary(version, wslDistribution)?.let {
+                return use(it)
+            }
         }
 
         this.getLocalBinary(null, wslDistribution)?.let {
+            val command = use(it)
+
             val message = latestSupportedVersion?.let { latest ->
                 "using a local installation with version ${it.version}, latest supported version is $latest"
             } ?: "using a possibly outdated local installation with version ${it.version}"
@@ -355,7 +413,7 @@ class MirrordBinaryManager {
                 .withDontShowAgain(MirrordSettingsState.NotificationId.POSSIBLY_OUTDATED_BINARY_USED)
                 .fire()
 
-            return it.command
+            return command
         }
 
         throw MirrordError(
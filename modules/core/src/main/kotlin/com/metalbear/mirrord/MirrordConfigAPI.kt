@@ -1,29 +1,30 @@
 package com.metalbear.mirrord
 
 import com.google.gson.Gson
+import com.intellij.execution.configurations.RunConfiguration
 import com.intellij.notification.NotificationType
+import com.intellij.openapi.components.service
 import com.intellij.openapi.project.Project
 import com.intellij.openapi.vfs.VirtualFile
 import java.nio.charset.Charset
 
 /**
  * For detecting mirrord config specified in run configuration.
+ * Superseded by [MirrordRunConfigurationOptions.configFile], still used when the option is not set.
  */
 const val CONFIG_ENV_NAME: String = "MIRRORD_CONFIG_FILE"
 
-private const val DEFAULT_CONFIG =
-    """{
-    "feature": {
-        "network": {
-            "incoming": "mirror",
-            "outgoing": true
-        },
-        "fs": "read",
-        "env": true
-    }
-}"""
+/**
+ * For enabling mirrord in a single run configuration with `MIRRORD_ACTIVE=1`.
+ * Superseded by [MirrordRunConfigurationOptions.enableMode], still used when the option is left at the default.
+ */
+const val ACTIVE_ENV_NAME: String = "MIRRORD_ACTIVE"
 
-class InvalidConfigException(path: String, reason: String) : MirrordError("failed to process config $path - $reason")
+class InvalidConfigException(path: String, reason: String) : MirrordError("failed to process config $path - $reason") {
+    init {
+        configPath = path
+    }
+}
 
 /**
  * Searches mirrord config for target.
@@ -49,9 +50,22 @@ class MirrordConfigAPI(private val service: MirrordProjectService) {
     /**
      * Searches for correct mirrord config path for a run configuration.
      * Displays notifications to the user.
-     * @param configFromEnv path to mirrord specified in the configuration.
+     * @param configFromEnv path to mirrord specified in the `MIRRORD_CONFIG_FILE` environment variable.
+     * @param configFromRunConfiguration path set in the mirrord tab of the run configuration, takes precedence over the active config
+     * @param configuration run configuration being started
      */
-    fun getConfigPath(configFromEnv: String?): String? {
+    fun getConfigPath(configFromEnv: String?, configFromRunConfiguration: String? = null, configuration: RunConfiguration? = null): String? {
+        configFromRunConfiguration?.let {
+            service.notifier.notification(
+                "Using mirrord config set in \"${configuration?.name}\"",
+                NotificationType.INFORMATION
+            )
+                .withOpenPath(it)
+                .withDontShowAgain(MirrordSettingsState.NotificationId.ACTIVE_CONFIG_USED).fire()
+
+            return it
+        }
+
         service.activeConfig?.let {
             service.notifier.notification(
                 "Using mirrord active config",
@@ -108,7 +122,7 @@ class MirrordConfigAPI(private val service: MirrordProjectService) {
      * Searches for the `.mirrord` directory in the project.
      * @throws InvalidProjectException if parent directory for `.mirrord` could not be found.
      */
-    private fun getMirrordDir(): VirtualFile? {
+    fun getMirrordDir(): VirtualFile? {
         return getProjectDir().findChild(".mirrord")?.takeIf { it.isDirectory }
     }
 
@@ -127,17 +141,45 @@ class MirrordConfigAPI(private val service: MirrordProjectService) {
 
     /**
      * Creates a default mirrord config in the given project.
-     * Config is located under `.mirrord/mirrord.json`.
+     * Config is located under `.mirrord/mirrord.json` and uses the [MirrordConfigTemplate.MIRROR] template.
      * @throws InvalidProjectException if parent directory for `.mirrord` could not be found.
      */
     fun createDefaultConfig(): VirtualFile {
-        val mirrordDir = getMirrordDir() ?: getProjectDir().createChildDirectory(this, ".mirrord")
+        return createConfig(null, "mirrord.json", MirrordConfigTemplate.MIRROR.render(MirrordConfigFormat.JSON))
+    }
 
-        return mirrordDir.createChildData(this, "mirrord.json")
+    /**
+     * Creates a mirrord config file. Must be called from a write action.
+     *
+     * @param directory where to create the file, null for the `.mirrord` directory, which is created if needed
+     * @throws InvalidProjectException if parent directory for `.mirrord` could not be found.
+     */
+    fun createConfig(directory: VirtualFile?, name: String, content: String): VirtualFile {
+        val parent = directory ?: getMirrordDir() ?: getProjectDir().createChildDirectory(this, ".mirrord")
+
+        return parent.createChildData(this, name)
             .apply { bom = null }
             .apply { charset = Charset.forName("UTF-8") }
-            .apply { setBinaryContent(DEFAULT_CONFIG.toByteArray()) }
+            .apply { setBinaryContent(content.toByteArray()) }
     }
+
+    /**
+     * Whether the file is a mirrord config of this project: a file in a `.mirrord` directory, the active config,
+     * or a config set in a run configuration, either in the mirrord tab or with [CONFIG_ENV_NAME].
+     * Narrower than [isConfigFilePath], for features that should not run on unrelated files.
+     */
+    fun isProjectConfig(file: VirtualFile): Boolean {
+        if (file.isDirectory || !isValidConfigExt(file)) {
+            return false
+        }
+
+        if (generateSequence(file.parent) { it.parent }.any { it.name == ".mirrord" } || service.activeConfig == file) {
+            return true
+        }
+
+        return service.project.service<MirrordConfigReferences>().isReferenced(file.path)
+    }
+
     companion object {
         fun isConfigFilePath(file: VirtualFile): Boolean {
             return file.path.contains("mirrord") && isValidConfigExt(file)
@@ -0,0 +1,138 @@
+package com.metalbear.mirrord
+
+import com.intellij.lang.annotation.AnnotationHolder
+import com.intellij.lang.annotation.ExternalAnnotator
+import com.intellij.lang.annotation.HighlightSeverity
+import com.intellij.notification.NotificationType
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.service
+import com.intellij.openapi.editor.Document
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.progress.EmptyProgressIndicator
+import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.progress.ProgressManager
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.SystemInfo
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.psi.PsiFile
+import java.util.concurrent.atomic.AtomicBoolean
+
+/**
+ * Runs `mirrord verify-config` on mirrord config files as the user types and highlights the reported errors and warnings.
+ * Only the configs of the project are verified, see [MirrordConfigAPI.isProjectConfig].
+ *
+ * Not available on Windows, where the mirrord binary runs in WSL: starting it on every change would be too slow,
+ * and the WSL distribution is known only from the run configuration. The user is told once to use the before launch task instead.
+ */
+class MirrordConfigAnnotator : ExternalAnnotator<MirrordConfigAnnotator.Info, MirrordConfigAnnotator.Result>() {
+    companion object {
+        private val windowsNoticeShown = AtomicBoolean(false)
+    }
+
+    /**
+     * Path of the local mirrord binary used to verify the configs. Looking it up runs the binaries found
+     * in `PATH` and in plugin storage, so it is done once and repeated only when another binary is used.
+     */
+    @Service(Service.Level.APP)
+    class BinaryCache : Disposable {
+        @Volatile
+        private var binary: String? = null
+
+        init {
+            service<MirrordBinaryManager>().addListener({ binary = null }, this)
+        }
+
+        /**
+         * @return the path to the local binary, null if no local binary was found
+         */
+        fun get(): String? {
+            binary?.let { return it }
+            return service<MirrordBinaryManager>().findLocalBinary(null).also { binary = it }
+        }
+
+        override fun dispose() {}
+    }
+
+    /**
+     * Collected on the event dispatch thread, used to verify the config in the background.
+     *
+     * @param extension extension of the config file, the mirrord binary uses it to pick the format
+     */
+    class Info(val project: Project, val text: String, val extension: String)
+
+    /**
+     * Output of `mirrord verify-config`.
+     */
+    class Result(val errors: List<String>, val warnings: List<String>)
+
+    override fun collectInformation(file: PsiFile, editor: Editor, hasErrors: Boolean): Info? {
+        val virtualFile = file.virtualFile ?: return null
+        val service = file.project.service<MirrordProjectService>()
+        if (!service.configApi.isProjectConfig(virtualFile)) {
+            return null
+        }
+
+        if (SystemInfo.isWindows) {
+            if (!windowsNoticeShown.getAndSet(true)) {
+                service
+                    .notifier
+                    .notification(
+                        "mirrord configs are not verified in the editor on Windows, since the mirrord binary runs in WSL. " +
+                            "Add the \"Verify mirrord config\" before launch task to verify the config when a run starts.",
+                        NotificationType.INFORMATION
+                    )
+                    .withDontShowAgain(MirrordSettingsState.NotificationId.EDITOR_VERIFICATION_UNAVAILABLE)
+                    .fire()
+            }
+            return null
+        }
+
+        return Info(file.project, editor.document.text, virtualFile.extension ?: "json")
+    }
+
+    override fun doAnnotate(info: Info): Result? {
+        val cli = service<BinaryCache>().get() ?: return null
+        val indicator = ProgressManager.getGlobalProgressIndicator() ?: EmptyProgressIndicator()
+
+        // The binary reads the config from disk, and the document may not be saved yet.
+        val tempFile = FileUtil.createTempFile("mirrord-config", ".${info.extension}", true)
+        return try {
+            tempFile.writeText(info.text)
+
+            val output = info
+                .project
+                .service<MirrordProjectService>()
+                .mirrordApi(null)
+                .verifyConfig(cli, tempFile.path, indicator)
+            val verified = MirrordVerifiedConfig(output, null)
+
+            Result(verified.errors.orEmpty(), verified.warnings.orEmpty())
+        } catch (e: ProcessCanceledException) {
+            throw e
+        } catch (e: Exception) {
+            MirrordLogger.logger.debug("failed to verify mirrord config in the editor", e)
+            null
+        } finally {
+            FileUtil.delete(tempFile)
+        }
+    }
+
+    override fun apply(file: PsiFile, annotationResult: Result, holder: AnnotationHolder) {
+        val document = file.viewProvider.document ?: return
+
+        annotationResult.errors.forEach { annotate(file, document, it, HighlightSeverity.ERROR, holder) }
+        annotationResult.warnings.forEach { annotate(file, document, it, HighlightSeverity.WARNING, holder) }
+    }
+
+    private fun annotate(file: PsiFile, document: Document, message: String, severity: HighlightSeverity, holder: AnnotationHolder) {
+        val builder = holder.newAnnotation(severity, "mirrord: $message")
+
+        val range = MirrordConfigLocator.findRange(file, document, message)
+        if (range != null) {
+            builder.range(range).create()
+        } else {
+            builder.fileLevel().create()
+        }
+    }
+}
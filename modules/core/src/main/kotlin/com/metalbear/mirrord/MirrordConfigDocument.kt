@@ -0,0 +1,378 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.core.JsonGenerator
+import com.fasterxml.jackson.core.util.DefaultIndenter
+import com.fasterxml.jackson.core.util.DefaultPrettyPrinter
+import com.fasterxml.jackson.databind.JsonNode
+import com.fasterxml.jackson.databind.ObjectMapper
+import com.fasterxml.jackson.databind.node.ObjectNode
+import com.fasterxml.jackson.dataformat.toml.TomlFactory
+import com.fasterxml.jackson.dataformat.yaml.YAMLFactory
+import com.fasterxml.jackson.dataformat.yaml.YAMLGenerator
+import com.intellij.openapi.vfs.VirtualFile
+
+/**
+ * Formats of the mirrord config file, see [MirrordConfigAPI.isValidConfigExt].
+ */
+enum class MirrordConfigFormat(val extension: String, private val mapper: ObjectMapper) {
+    JSON("json", ObjectMapper()),
+    YAML(
+        "yaml",
+        ObjectMapper(
+            YAMLFactory()
+                .disable(YAMLGenerator.Feature.WRITE_DOC_START_MARKER)
+                .enable(YAMLGenerator.Feature.MINIMIZE_QUOTES)
+        )
+    ),
+    TOML("toml", ObjectMapper(TomlFactory()));
+
+    companion object {
+        /**
+         * @return null if the file is not a json, yaml or toml file
+         */
+        fun of(file: VirtualFile): MirrordConfigFormat? = values().find { it.extension == file.extension }
+
+        /**
+         * @return null if the path does not point to a json, yaml or toml file
+         */
+        fun ofPath(path: String): MirrordConfigFormat? = values().find { path.endsWith(".${it.extension}") }
+    }
+
+    /**
+     * @throws InvalidConfigException if the text is not a valid config in this format
+     */
+    fun parse(path: String, text: String): ObjectNode {
+        if (text.isBlank()) {
+            return mapper.createObjectNode()
+        }
+
+        val root = try {
+            mapper.readTree(text)
+        } catch (e: Exception) {
+            throw InvalidConfigException(path, e.message?.lineSequence()?.firstOrNull() ?: "invalid ${name.lowercase()}")
+        }
+
+        return when {
+            // A yaml file with only comments has no document.
+            root == null || root.isMissingNode -> mapper.createObjectNode()
+            else -> root as? ObjectNode ?: throw InvalidConfigException(path, "the config must be an object")
+        }
+    }
+
+    /**
+     * Writes the whole config, comments and the original formatting are not preserved.
+     * Used for new files, existing files are edited with a [MirrordConfigPsiEditor].
+     */
+    fun write(root: ObjectNode): String {
+        return when (this) {
+            // Same formatting as the default config created by the plugin.
+            JSON -> mapper.writer(JsonPrettyPrinter()).writeValueAsString(root) + "\n"
+            else -> mapper.writeValueAsString(root)
+        }
+    }
+
+    /**
+     * Writes a single value, to be inserted into an existing file.
+     * Objects are written in the block style in yaml, and as inline tables in toml.
+     */
+    fun writeValue(value: JsonNode): String {
+        return when (this) {
+            JSON -> mapper.writer(JsonPrettyPrinter()).writeValueAsString(value)
+            YAML -> mapper.writeValueAsString(value).trimEnd()
+            TOML -> tomlValue(value)
+        }
+    }
+}
+
+/**
+ * Keys that don't have to be quoted in toml.
+ */
+private val TOML_BARE_KEY_REGEX = Regex("[A-Za-z0-9_-]+")
+
+/**
+ * @return the key, quoted if needed
+ */
+fun tomlKey(key: String): String = if (TOML_BARE_KEY_REGEX.matches(key)) key else tomlString(key)
+
+private fun tomlString(value: String): String {
+    val escaped = buildString {
+        value.forEach {
+            when {
+                it == '\\' -> append("\\\\")
+                it == '"' -> append("\\\"")
+                it == '\n' -> append("\\n")
+                it == '\t' -> append("\\t")
+                it < ' ' -> append("\\u%04x".format(it.code))
+                else -> append(it)
+            }
+        }
+    }
+    return "\"$escaped\""
+}
+
+private fun tomlValue(value: JsonNode): String {
+    return when {
+        value.isTextual -> tomlString(value.asText())
+        value.isArray -> value.joinToString(", ", "[", "]") { tomlValue(it) }
+        value.isObject -> value.fields().asSequence().joinToString(", ", "{ ", " }") { (key, item) -> "${tomlKey(key)} = ${tomlValue(item)}" }
+        else -> value.asText()
+    }
+}
+
+/**
+ * A change of a single value in the config, applied to the file by a [MirrordConfigPsiEditor].
+ *
+ * @param path keys from the root of the config to the changed value
+ * @param value the new value, null if the key is removed
+ */
+data class MirrordConfigEdit(val path: List<String>, val value: JsonNode?) {
+    companion object {
+        /**
+         * @return the edits that turn [old] into [new], objects present in both are edited key by key
+         */
+        fun diff(old: ObjectNode, new: ObjectNode, path: List<String> = emptyList()): List<MirrordConfigEdit> {
+            val edits = mutableListOf<MirrordConfigEdit>()
+            old.fieldNames().forEach {
+                if (!new.has(it)) {
+                    edits.add(MirrordConfigEdit(path + it, null))
+                }
+            }
+            new.fields().forEach { (key, value) ->
+                val previous = old.get(key)
+                when {
+                    previous == value -> {}
+                    previous is ObjectNode && value is ObjectNode -> edits.addAll(diff(previous, value, path + key))
+                    else -> edits.add(MirrordConfigEdit(path + key, value))
+                }
+            }
+            return edits
+        }
+    }
+}
+
+/**
+ * Indents with 4 spaces and writes `"key": value` instead of the default `"key" : value`.
+ */
+private class JsonPrettyPrinter : DefaultPrettyPrinter() {
+    init {
+        indentObjectsWith(DefaultIndenter("    ", "\n"))
+    }
+
+    override fun createInstance(): DefaultPrettyPrinter = JsonPrettyPrinter()
+
+    override fun writeObjectFieldValueSeparator(g: JsonGenerator) {
+        g.writeRaw(": ")
+    }
+}
+
+/**
+ * Options of a mirrord config that can be edited in [MirrordConfigFormEditor].
+ * A null value means that the option is not set in the config and the mirrord default is used.
+ */
+data class MirrordConfigFormModel(
+    val targetPath: String? = null,
+    val targetNamespace: String? = null,
+    val agentNamespace: String? = null,
+    val agentLogLevel: String? = null,
+    val agentEphemeral: Boolean? = null,
+    val incomingMode: String? = null,
+    val incomingHeaderFilter: String? = null,
+    val outgoingTcp: Boolean? = null,
+    val outgoingUdp: Boolean? = null,
+    val fsMode: String? = null,
+    val envEnabled: Boolean? = null,
+    val envInclude: String? = null,
+    val envExclude: String? = null
+) {
+    companion object {
+        val INCOMING_MODES = listOf("mirror", "steal", "off")
+
+        val FS_MODES = listOf("read", "write", "local", "localwithoverrides")
+
+        val LOG_LEVELS = listOf("error", "warn", "info", "debug", "trace")
+
+        private val ENV_FORM_KEYS = setOf("include", "exclude")
+
+        /**
+         * Whether the env object holds options the form does not show (e.g. `override`),
+         * the remote environment cannot be disabled from the form without losing them.
+         */
+        fun hasEnvOptionsOutsideForm(root: ObjectNode): Boolean {
+            return (root.path("feature").path("env") as? ObjectNode)?.hasOtherThan(ENV_FORM_KEYS) ?: false
+        }
+
+        /**
+         * Reads the options from the config, both the short (e.g. `"incoming": "steal"`)
+         * and the long (e.g. `"incoming": { "mode": "steal" }`) forms are supported.
+         */
+        fun read(root: ObjectNode): MirrordConfigFormModel {
+            val target = root.path("target")
+            val agent = root.path("agent")
+            val network = root.path("feature").path("network")
+            val incoming = network.path("incoming")
+            val outgoing = network.path("outgoing")
+            val fs = root.path("feature").path("fs")
+            val env = root.path("feature").path("env")
+
+            return MirrordConfigFormModel(
+                targetPath = target.textOrNull() ?: target.path("path").textOrNull(),
+                targetNamespace = target.path("namespace").textOrNull(),
+                agentNamespace = agent.path("namespace").textOrNull(),
+                agentLogLevel = agent.path("log_level").textOrNull(),
+                agentEphemeral = agent.path("ephemeral").booleanOrNull(),
+                incomingMode = incoming.textOrNull() ?: incoming.path("mode").textOrNull(),
+                incomingHeaderFilter = incoming.path("http_filter").path("header_filter").textOrNull(),
+                outgoingTcp = outgoing.booleanOrNull() ?: outgoing.path("tcp").booleanOrNull(),
+                outgoingUdp = outgoing.booleanOrNull() ?: outgoing.path("udp").booleanOrNull(),
+                fsMode = fs.textOrNull() ?: fs.path("mode").textOrNull(),
+                envEnabled = env.booleanOrNull() ?: if (env.isObject) true else null,
+                envInclude = env.path("include").listOrNull(),
+                envExclude = env.path("exclude").listOrNull()
+            )
+        }
+
+        private fun JsonNode.textOrNull(): String? = takeIf { it.isTextual }?.asText()
+
+        private fun JsonNode.booleanOrNull(): Boolean? = takeIf { it.isBoolean }?.asBoolean()
+
+        /**
+         * Lists of variables can be given as an array or as a `;` separated string.
+         */
+        private fun JsonNode.listOrNull(): String? = when {
+            isTextual -> asText()
+            isArray -> joinToString(";") { it.asText() }
+            else -> null
+        }
+    }
+
+    /**
+     * Writes the options to the config. Only the sections that differ from [previous] are modified,
+     * so that the parts of the config the form does not know about stay as they are.
+     */
+    fun applyTo(root: ObjectNode, previous: MirrordConfigFormModel) {
+        if (targetPath != previous.targetPath || targetNamespace != previous.targetNamespace) {
+            applyTarget(root)
+        }
+
+        if (agentNamespace != previous.agentNamespace || agentLogLevel != previous.agentLogLevel || agentEphemeral != previous.agentEphemeral) {
+            val agent = root.objectAt("agent")
+            agent.putOrRemove("namespace", agentNamespace)
+            agent.putOrRemove("log_level", agentLogLevel)
+            agent.putOrRemove("ephemeral", agentEphemeral)
+            root.removeIfEmpty("agent")
+        }
+
+        val feature = root.objectAt("feature")
+        val network = feature.objectAt("network")
+        if (incomingMode != previous.incomingMode || incomingHeaderFilter != previous.incomingHeaderFilter) {
+            applyIncoming(network)
+        }
+
+        if (outgoingTcp != previous.outgoingTcp || outgoingUdp != previous.outgoingUdp) {
+            val outgoing = network.get("outgoing")
+            when {
+                // Keeps the options the form does not know about, e.g. `filter` or `ignore_localhost`.
+                outgoing is ObjectNode -> {
+                    outgoing.putOrRemove("tcp", outgoingTcp)
+                    outgoing.putOrRemove("udp", outgoingUdp)
+                    network.removeIfEmpty("outgoing")
+                }
+                outgoingTcp == outgoingUdp -> network.putOrRemove("outgoing", outgoingTcp)
+                else -> {
+                    val outgoingObject = network.putObject("outgoing")
+                    outgoingObject.putOrRemove("tcp", outgoingTcp)
+                    outgoingObject.putOrRemove("udp", outgoingUdp)
+                }
+            }
+        }
+        feature.removeIfEmpty("network")
+
+        if (fsMode != previous.fsMode) {
+            val fs = feature.get("fs")
+            if (fs is ObjectNode) {
+                fs.putOrRemove("mode", fsMode)
+                feature.removeIfEmpty("fs")
+            } else {
+                feature.putOrRemove("fs", fsMode)
+            }
+        }
+
+        if (envEnabled != previous.envEnabled || envInclude != previous.envInclude || envExclude != previous.envExclude) {
+            applyEnv(feature)
+        }
+        root.removeIfEmpty("feature")
+    }
+
+    private fun applyTarget(root: ObjectNode) {
+        val target = root.get("target")
+        when {
+            targetPath == null && targetNamespace == null -> root.remove("target")
+            targetNamespace == null && target !is ObjectNode -> root.put("target", targetPath)
+            else -> {
+                val targetObject = root.objectAt("target")
+                targetObject.putOrRemove("path", targetPath)
+                targetObject.putOrRemove("namespace", targetNamespace)
+            }
+        }
+    }
+
+    /**
+     * An env object is edited key by key, so that e.g. `override` or `env_file` are kept.
+     * It's replaced with `false` only if it holds nothing but the options of the form, see [hasEnvOptionsOutsideForm].
+     */
+    private fun applyEnv(feature: ObjectNode) {
+        val env = feature.get("env")
+        if (env is ObjectNode) {
+            env.putOrRemove("include", envInclude)
+            env.putOrRemove("exclude", envExclude)
+            if (env.size() == 0 || (envEnabled == false && !env.hasOtherThan(ENV_FORM_KEYS))) {
+                feature.putOrRemove("env", envEnabled)
+            }
+        } else if (envEnabled != false && (envInclude != null || envExclude != null)) {
+            val envObject = feature.putObject("env")
+            envObject.putOrRemove("include", envInclude)
+            envObject.putOrRemove("exclude", envExclude)
+        } else {
+            feature.putOrRemove("env", envEnabled)
+        }
+    }
+
+    private fun applyIncoming(network: ObjectNode) {
+        val incoming = network.get("incoming")
+        when {
+            incomingHeaderFilter != null || incoming is ObjectNode -> {
+                val incomingObject = network.objectAt("incoming")
+                incomingObject.putOrRemove("mode", incomingMode)
+                incomingObject.objectAt("http_filter").putOrRemove("header_filter", incomingHeaderFilter)
+                incomingObject.removeIfEmpty("http_filter")
+                network.removeIfEmpty("incoming")
+            }
+            else -> network.putOrRemove("incoming", incomingMode)
+        }
+    }
+}
+
+/**
+ * Returns the object under the given key, replacing a value of another type (e.g. `"env": true`).
+ */
+private fun ObjectNode.objectAt(name: String): ObjectNode {
+    return get(name) as? ObjectNode ?: putObject(name)
+}
+
+private fun ObjectNode.putOrRemove(name: String, value: String?) {
+    if (value == null) remove(name) else put(name, value)
+}
+
+private fun ObjectNode.putOrRemove(name: String, value: Boolean?) {
+    if (value == null) remove(name) else put(name, value)
+}
+
+private fun ObjectNode.hasOtherThan(names: Set<String>): Boolean {
+    return fieldNames().asSequence().any { it !in names }
+}
+
+private fun ObjectNode.removeIfEmpty(name: String) {
+    if ((get(name) as? ObjectNode)?.size() == 0) {
+        remove(name)
+    }
+}
@@ -0,0 +1,37 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.fileEditor.FileEditor
+import com.intellij.openapi.fileEditor.FileEditorPolicy
+import com.intellij.openapi.fileEditor.FileEditorProvider
+import com.intellij.openapi.fileEditor.TextEditor
+import com.intellij.openapi.fileEditor.TextEditorWithPreview
+import com.intellij.openapi.fileEditor.impl.text.TextEditorProvider
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.vfs.VirtualFile
+
+/**
+ * Opens mirrord config files in a split editor, with the text on one side and [MirrordConfigFormEditor] on the other.
+ */
+class MirrordConfigEditorProvider : FileEditorProvider, DumbAware {
+    /**
+     * Stricter than [MirrordConfigAPI.isConfigFilePath], which accepts every file in a project with `mirrord` in the path.
+     */
+    override fun accept(project: Project, file: VirtualFile): Boolean {
+        return MirrordConfigAPI.isValidConfigExt(file) && (file.name.contains("mirrord") || file.parent?.name == ".mirrord")
+    }
+
+    override fun createEditor(project: Project, file: VirtualFile): FileEditor {
+        val textEditor = TextEditorProvider.getInstance().createEditor(project, file) as TextEditor
+        return TextEditorWithPreview(
+            textEditor,
+            MirrordConfigFormEditor(project, file),
+            "mirrord Config",
+            TextEditorWithPreview.Layout.SHOW_EDITOR_AND_PREVIEW
+        )
+    }
+
+    override fun getEditorTypeId(): String = "mirrord-config-form"
+
+    override fun getPolicy(): FileEditorPolicy = FileEditorPolicy.HIDE_DEFAULT_EDITOR
+}
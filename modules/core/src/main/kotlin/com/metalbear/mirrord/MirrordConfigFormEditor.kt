@@ -0,0 +1,320 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.node.ObjectNode
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.command.WriteCommandAction
+import com.intellij.openapi.editor.Document
+import com.intellij.openapi.editor.event.DocumentEvent
+import com.intellij.openapi.editor.event.DocumentListener
+import com.intellij.openapi.fileEditor.FileDocumentManager
+import com.intellij.openapi.fileEditor.FileEditor
+import com.intellij.openapi.fileEditor.FileEditorState
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.ComboBox
+import com.intellij.openapi.util.UserDataHolderBase
+import com.intellij.openapi.vfs.VirtualFile
+import com.intellij.psi.PsiDocumentManager
+import com.intellij.psi.PsiManager
+import com.intellij.ui.DocumentAdapter
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.SimpleListCellRenderer
+import com.intellij.ui.components.JBLabel
+import com.intellij.ui.components.JBTextField
+import com.intellij.util.Alarm
+import com.intellij.util.ui.FormBuilder
+import com.intellij.util.ui.JBUI
+import com.intellij.util.ui.UIUtil
+import java.awt.BorderLayout
+import java.beans.PropertyChangeListener
+import javax.swing.JComponent
+import javax.swing.JPanel
+import javax.swing.text.JTextComponent
+
+/**
+ * How long the form waits after the last change before syncing with the text.
+ */
+private const val SYNC_DELAY_MILLIS = 300
+
+/**
+ * Combo box for an optional value, the first item leaves the option unset.
+ */
+private class OptionBox<T : Any>(values: List<T>, presentable: (T) -> String = { it.toString() }) : ComboBox<Any>(arrayOf<Any>(UNSET) + values) {
+    companion object {
+        private val UNSET = Any()
+    }
+
+    init {
+        @Suppress("UNCHECKED_CAST")
+        renderer = SimpleListCellRenderer.create("") { if (it === UNSET) "(default)" else presentable(it as T) }
+    }
+
+    @Suppress("UNCHECKED_CAST")
+    var value: T?
+        get() = selectedItem.takeIf { it !== UNSET } as T?
+        set(value) {
+            selectedItem = value ?: UNSET
+        }
+}
+
+/**
+ * Form for the common options of a mirrord config, displayed next to the text in [MirrordConfigEditorProvider].
+ * Changes in the form are written to the document and changes in the document are shown in the form.
+ */
+class MirrordConfigFormEditor(private val project: Project, private val file: VirtualFile) : UserDataHolderBase(), FileEditor {
+    private val format = MirrordConfigFormat.of(file)
+
+    private val document: Document? = FileDocumentManager.getInstance().getDocument(file)
+
+    private val targetPath = JBTextField().apply { emptyText.text = "e.g. deployment/my-app" }
+    private val targetNamespace = JBTextField()
+    private val agentNamespace = JBTextField()
+    private val agentLogLevel = OptionBox(MirrordConfigFormModel.LOG_LEVELS)
+    private val agentEphemeral = OptionBox(listOf(true, false)) { if (it) "Enabled" else "Disabled" }
+    private val incomingMode = OptionBox(MirrordConfigFormModel.INCOMING_MODES)
+    private val incomingHeaderFilter = JBTextField().apply { emptyText.text = "e.g. x-user: me, steals only the matching HTTP requests" }
+    private val outgoingTcp = OptionBox(listOf(true, false)) { if (it) "Enabled" else "Disabled" }
+    private val outgoingUdp = OptionBox(listOf(true, false)) { if (it) "Enabled" else "Disabled" }
+    private val fsMode = OptionBox(MirrordConfigFormModel.FS_MODES)
+    private val envEnabled = OptionBox(listOf(true, false)) { if (it) "Enabled" else "Disabled" }
+    private val envInclude = JBTextField().apply { emptyText.text = "e.g. DATABASE_*;API_URL" }
+    private val envExclude = JBTextField()
+
+    private val errorLabel = JBLabel().apply {
+        icon = AllIcons.General.Error
+        border = JBUI.Borders.empty(5, 10)
+        isVisible = false
+    }
+
+    private val form: JPanel = FormBuilder
+        .createFormBuilder()
+        .addSeparator()
+        .addComponent(JBLabel("Target"))
+        .addLabeledComponent("Path:", targetPath)
+        .addLabeledComponent("Namespace:", targetNamespace)
+        .addSeparator()
+        .addComponent(JBLabel("Agent"))
+        .addLabeledComponent("Namespace:", agentNamespace)
+        .addLabeledComponent("Log level:", agentLogLevel)
+        .addLabeledComponent("Ephemeral container:", agentEphemeral)
+        .addSeparator()
+        .addComponent(JBLabel("Network"))
+        .addLabeledComponent("Incoming mode:", incomingMode)
+        .addLabeledComponent("HTTP header filter:", incomingHeaderFilter)
+        .addLabeledComponent("Outgoing TCP:", outgoingTcp)
+        .addLabeledComponent("Outgoing UDP:", outgoingUdp)
+        .addSeparator()
+        .addComponent(JBLabel("File System"))
+        .addLabeledComponent("Mode:", fsMode)
+        .addSeparator()
+        .addComponent(JBLabel("Environment"))
+        .addLabeledComponent("Remote environment:", envEnabled)
+        .addLabeledComponent("Include:", envInclude)
+        .addLabeledComponent("Exclude:", envExclude)
+        .panel
+        .apply { border = JBUI.Borders.empty(0, 10, 10, 10) }
+
+    private val component = JPanel(BorderLayout()).apply {
+        add(errorLabel, BorderLayout.NORTH)
+        add(ScrollPaneFactory.createScrollPane(JPanel(BorderLayout()).apply { add(form, BorderLayout.NORTH) }, true), BorderLayout.CENTER)
+    }
+
+    private val syncAlarm = Alarm(Alarm.ThreadToUse.SWING_THREAD, this)
+
+    /**
+     * Options as they were last read from or written to the document.
+     */
+    private var model = MirrordConfigFormModel()
+
+    /**
+     * Set while the form is being filled from the document, so that it is not written back.
+     */
+    private var loading = false
+
+    /**
+     * Set while the form writes to the document, so that the change is not read back.
+     */
+    private var saving = false
+
+    init {
+        val onFormChange = { scheduleSync { saveToDocument() } }
+        listOf(targetPath, targetNamespace, agentNamespace, incomingHeaderFilter, envInclude, envExclude).forEach {
+            it.document.addDocumentListener(object : DocumentAdapter() {
+                override fun textChanged(e: javax.swing.event.DocumentEvent) {
+                    if (!loading) onFormChange()
+                }
+            })
+        }
+        listOf(agentLogLevel, agentEphemeral, incomingMode, outgoingTcp, outgoingUdp, fsMode, envEnabled).forEach {
+            it.addActionListener { if (!loading) onFormChange() }
+        }
+
+        document?.addDocumentListener(
+            object : DocumentListener {
+                override fun documentChanged(event: DocumentEvent) {
+                    if (!saving) scheduleSync { loadFromDocument() }
+                }
+            },
+            this
+        )
+
+        loadFromDocument()
+    }
+
+    private fun scheduleSync(sync: () -> Unit) {
+        if (syncAlarm.isDisposed) {
+            return
+        }
+
+        syncAlarm.cancelAllRequests()
+        syncAlarm.addRequest(Runnable { sync() }, SYNC_DELAY_MILLIS, ModalityState.any())
+    }
+
+    private fun showError(message: String?) {
+        errorLabel.text = message
+        errorLabel.isVisible = message != null
+        UIUtil.setEnabled(form, message == null, true)
+    }
+
+    private fun loadFromDocument() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+        val document = document ?: return showError("The config file could not be opened")
+        val format = format ?: return showError("Only json, yaml and toml configs can be edited in the form")
+        if (psiEditor() == null) {
+            return showError("Install the ${format.name} plugin to edit this config in the form")
+        }
+
+        val root = try {
+            format.parse(file.path, document.text)
+        } catch (e: InvalidConfigException) {
+            return showError("The config could not be parsed, fix it in the text editor: ${e.richMessage}")
+        }
+        showError(null)
+
+        model = MirrordConfigFormModel.read(root)
+        loading = true
+        try {
+            setText(targetPath, model.targetPath)
+            setText(targetNamespace, model.targetNamespace)
+            setText(agentNamespace, model.agentNamespace)
+            agentLogLevel.value = model.agentLogLevel
+            agentEphemeral.value = model.agentEphemeral
+            incomingMode.value = model.incomingMode
+            setText(incomingHeaderFilter, model.incomingHeaderFilter)
+            outgoingTcp.value = model.outgoingTcp
+            outgoingUdp.value = model.outgoingUdp
+            fsMode.value = model.fsMode
+            envEnabled.value = model.envEnabled
+            setText(envInclude, model.envInclude)
+            setText(envExclude, model.envExclude)
+
+            val envLocked = MirrordConfigFormModel.hasEnvOptionsOutsideForm(root)
+            envEnabled.isEnabled = !envLocked
+            envEnabled.toolTipText = if (envLocked) "The env section has options the form does not show, edit it in the text editor" else null
+        } finally {
+            loading = false
+        }
+    }
+
+    private fun psiEditor(): MirrordConfigPsiEditor? {
+        return PsiManager.getInstance(project).findFile(file)?.let { MirrordConfigPsiEditor.forFile(it) }
+    }
+
+    /**
+     * Does not touch the field if the text is the same, so that the caret stays where it is.
+     */
+    private fun setText(field: JTextComponent, value: String?) {
+        if (field.text != value.orEmpty()) {
+            field.text = value.orEmpty()
+        }
+    }
+
+    private fun formModel(): MirrordConfigFormModel {
+        return MirrordConfigFormModel(
+            targetPath = targetPath.text.trim().ifEmpty { null },
+            targetNamespace = targetNamespace.text.trim().ifEmpty { null },
+            agentNamespace = agentNamespace.text.trim().ifEmpty { null },
+            agentLogLevel = agentLogLevel.value,
+            agentEphemeral = agentEphemeral.value,
+            incomingMode = incomingMode.value,
+            incomingHeaderFilter = incomingHeaderFilter.text.trim().ifEmpty { null },
+            outgoingTcp = outgoingTcp.value,
+            outgoingUdp = outgoingUdp.value,
+            fsMode = fsMode.value,
+            envEnabled = envEnabled.value,
+            envInclude = envInclude.text.trim().ifEmpty { null },
+            envExclude = envExclude.text.trim().ifEmpty { null }
+        )
+    }
+
+    private fun saveToDocument() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+        val document = document ?: return
+        val format = format ?: return
+
+        val updated = formModel()
+        if (updated == model) {
+            return
+        }
+
+        val root = try {
+            format.parse(file.path, document.text)
+        } catch (e: InvalidConfigException) {
+            return showError("The config could not be parsed, fix it in the text editor: ${e.richMessage}")
+        }
+        val updatedRoot = root.deepCopy()
+        updated.applyTo(updatedRoot, model)
+        model = updated
+
+        val edits = MirrordConfigEdit.diff(root, updatedRoot)
+        if (edits.isEmpty()) {
+            return
+        }
+
+        saving = true
+        try {
+            WriteCommandAction.runWriteCommandAction(project, "Edit mirrord Config", null, Runnable { applyEdits(document, format, edits, updatedRoot) })
+        } finally {
+            saving = false
+        }
+    }
+
+    /**
+     * Only the edited values are changed, so that comments and formatting are kept.
+     */
+    private fun applyEdits(document: Document, format: MirrordConfigFormat, edits: List<MirrordConfigEdit>, updatedRoot: ObjectNode) {
+        val documentManager = PsiDocumentManager.getInstance(project)
+        documentManager.commitDocument(document)
+        val psiFile = documentManager.getPsiFile(document) ?: return
+        val psiEditor = MirrordConfigPsiEditor.forFile(psiFile) ?: return
+
+        if (psiEditor.apply(psiFile, document, edits)) {
+            documentManager.doPostponedOperationsAndUnblockDocument(document)
+        } else {
+            // There is no object in the file yet, only whitespace and comments which are kept.
+            val separator = if (document.textLength == 0 || document.text.endsWith("\n")) "" else "\n"
+            document.insertString(document.textLength, separator + format.write(updatedRoot))
+        }
+    }
+
+    override fun getComponent(): JComponent = component
+
+    override fun getPreferredFocusedComponent(): JComponent = targetPath
+
+    override fun getName(): String = "Form"
+
+    override fun getFile(): VirtualFile = file
+
+    override fun setState(state: FileEditorState) {}
+
+    override fun isModified(): Boolean = false
+
+    override fun isValid(): Boolean = file.isValid
+
+    override fun addPropertyChangeListener(listener: PropertyChangeListener) {}
+
+    override fun removePropertyChangeListener(listener: PropertyChangeListener) {}
+
+    override fun dispose() {}
+}
@@ -0,0 +1,69 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.application.ReadAction
+import com.intellij.openapi.editor.Document
+import com.intellij.openapi.fileEditor.FileDocumentManager
+import com.intellij.openapi.fileEditor.OpenFileDescriptor
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.TextRange
+import com.intellij.openapi.vfs.LocalFileSystem
+import com.intellij.psi.PsiFile
+import com.intellij.psi.PsiManager
+
+/**
+ * Errors produced by serde contain the position, for example `invalid type: string "a", expected a boolean at line 3 column 12`.
+ */
+private val POSITION_REGEX = Regex("""line (\d+) column (\d+)""")
+
+/**
+ * Errors and warnings often mention the config key in backticks or quotes, for example `feature.network.incoming`.
+ */
+private val KEY_REGEX = Regex("""[`"']([A-Za-z_][\w.\-]*)[`"']""")
+
+/**
+ * Maps the errors and warnings of `mirrord verify-config` to the parts of the config file they are about.
+ */
+object MirrordConfigLocator {
+    /**
+     * Finds the part of the config the message is about, the mirrord binary does not report exact positions.
+     * Keys mentioned in the message are resolved by their full path with [MirrordConfigPsiEditor.findKey].
+     * Must be called in a read action.
+     *
+     * @return null if the message could not be mapped to the config, or the language plugin of the config is not available
+     */
+    fun findRange(file: PsiFile, document: Document, message: String): TextRange? {
+        POSITION_REGEX.find(message)?.let { match ->
+            val line = match.groupValues[1].toInt() - 1
+            if (line in 0 until document.lineCount) {
+                val lineStart = document.getLineStartOffset(line)
+                val lineEnd = document.getLineEndOffset(line)
+                val start = (lineStart + match.groupValues[2].toInt() - 1).coerceIn(lineStart, lineEnd)
+                return TextRange(start, lineEnd).takeIf { !it.isEmpty } ?: TextRange(lineStart, lineEnd)
+            }
+        }
+
+        val editor = MirrordConfigPsiEditor.forFile(file) ?: return null
+        return KEY_REGEX
+            .findAll(message)
+            .mapNotNull { match -> editor.findKey(file, match.groupValues[1].split('.'))?.textRange }
+            .firstOrNull()
+    }
+
+    /**
+     * Opens the config file in the editor, at the part the message is about if it can be found.
+     * Must be called on the event dispatch thread.
+     *
+     * @throws MirrordError if the config file does not exist
+     */
+    fun navigate(project: Project, configPath: String, message: String) {
+        val file = LocalFileSystem.getInstance().refreshAndFindFileByPath(configPath)
+            ?: throw MirrordError("file $configPath not found")
+
+        val offset = ReadAction.compute<Int, RuntimeException> {
+            val document = FileDocumentManager.getInstance().getDocument(file)
+            val psiFile = PsiManager.getInstance(project).findFile(file)
+            document?.let { psiFile?.let { findRange(psiFile, document, message) } }?.startOffset ?: 0
+        }
+        OpenFileDescriptor(project, file, offset).navigate(true)
+    }
+}
@@ -0,0 +1,40 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.editor.Document
+import com.intellij.openapi.extensions.ExtensionPointName
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+
+/**
+ * Applies [MirrordConfigEdit]s to a config file in place, so that comments and formatting outside the edited values are kept,
+ * and finds the keys of the config for [MirrordConfigLocator].
+ * There is one implementation per config language, registered only when the language plugin is available.
+ */
+interface MirrordConfigPsiEditor {
+    companion object {
+        val EP_NAME: ExtensionPointName<MirrordConfigPsiEditor> = ExtensionPointName.create("com.metalbear.mirrord.configPsiEditor")
+
+        /**
+         * @return null if the language plugin of the file is not available
+         */
+        fun forFile(file: PsiFile): MirrordConfigPsiEditor? = EP_NAME.extensionList.find { it.accepts(file) }
+    }
+
+    fun accepts(file: PsiFile): Boolean
+
+    /**
+     * Called in a write command, with the document committed.
+     * Implementations modify either the PSI or the document, not both.
+     *
+     * @return false if the file has no top-level object to edit, e.g. it contains only comments
+     */
+    fun apply(file: PsiFile, document: Document, edits: List<MirrordConfigEdit>): Boolean
+
+    /**
+     * Called in a read action.
+     *
+     * @param path full path of the key, e.g. `["feature", "network", "incoming", "mode"]`
+     * @return the key of the value at the path, null if the config does not set it
+     */
+    fun findKey(file: PsiFile, path: List<String>): PsiElement?
+}
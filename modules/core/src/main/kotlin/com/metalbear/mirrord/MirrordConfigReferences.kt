@@ -0,0 +1,126 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.CommonProgramRunConfigurationParameters
+import com.intellij.execution.RunManager
+import com.intellij.execution.RunManagerListener
+import com.intellij.execution.RunnerAndConfigurationSettings
+import com.intellij.execution.configurations.RunConfiguration
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.openapi.vfs.AsyncFileListener
+import com.intellij.openapi.vfs.VirtualFileManager
+import com.intellij.openapi.vfs.newvfs.events.VFileContentChangeEvent
+import com.intellij.openapi.vfs.newvfs.events.VFileEvent
+
+/**
+ * Config files set in the run configurations of the project, with the path macros expanded.
+ *
+ * Expanding the macros evaluates the working directory and the macros of every run configuration,
+ * and the paths are checked for every highlighted file and on every VFS change,
+ * so they are cached until the run configurations change or files are created, moved or deleted.
+ */
+@Service(Service.Level.PROJECT)
+class MirrordConfigReferences(private val project: Project) : Disposable {
+    /**
+     * @param configFile expanded [MirrordRunConfigurationOptions.configFile], null if not set or it can't be expanded
+     * @param envConfigFile expanded [CONFIG_ENV_NAME] from the environment of the run configuration, null if not set
+     */
+    class Reference(val configuration: RunConfiguration, val configFile: String?, val envConfigFile: String?) {
+        /**
+         * The config used by the run configuration instead of the active config, null if there is none.
+         */
+        val configPath: String?
+            get() = configFile ?: envConfigFile
+    }
+
+    /**
+     * Invalidates the references when a run configuration is added, removed or edited.
+     */
+    class Listener(private val project: Project) : RunManagerListener {
+        override fun runConfigurationAdded(settings: RunnerAndConfigurationSettings) {
+            project.service<MirrordConfigReferences>().invalidate()
+        }
+
+        override fun runConfigurationRemoved(settings: RunnerAndConfigurationSettings) {
+            project.service<MirrordConfigReferences>().invalidate()
+        }
+
+        override fun runConfigurationChanged(settings: RunnerAndConfigurationSettings) {
+            project.service<MirrordConfigReferences>().invalidate()
+        }
+
+        override fun stateLoaded(runManager: RunManager, isFirstLoadState: Boolean) {
+            project.service<MirrordConfigReferences>().invalidate()
+        }
+    }
+
+    /**
+     * Macros such as `$ContentRoot$` depend on the files of the project, the references are invalidated
+     * when files are created, moved or deleted. Content changes do not affect them.
+     */
+    private inner class FileWatch : AsyncFileListener {
+        override fun prepareChange(events: MutableList<out VFileEvent>): AsyncFileListener.ChangeApplier? {
+            if (references == null || events.all { it is VFileContentChangeEvent }) {
+                return null
+            }
+
+            return object : AsyncFileListener.ChangeApplier {
+                override fun afterVfsChange() {
+                    invalidate()
+                }
+            }
+        }
+    }
+
+    @Volatile
+    private var references: List<Reference>? = null
+
+    init {
+        VirtualFileManager.getInstance().addAsyncFileListener(FileWatch(), this)
+    }
+
+    /**
+     * @return references of all run configurations that have a config set
+     */
+    fun references(): List<Reference> {
+        references?.let { return it }
+
+        return RunManager
+            .getInstance(project)
+            .allConfigurationsList
+            .map { configuration ->
+                val base = configuration as? RunConfigurationBase<*>
+                val configFile = base?.let { MirrordRunConfigurationOptions.get(it).configFile }
+                val envConfigFile = (configuration as? CommonProgramRunConfigurationParameters)?.envs?.get(CONFIG_ENV_NAME)
+                Reference(configuration, configFile?.let { expand(base, it) }, envConfigFile?.let { expand(base, it) })
+            }
+            .filter { it.configPath != null }
+            .also { references = it }
+    }
+
+    /**
+     * @return whether a run configuration uses the config at the given path
+     */
+    fun isReferenced(path: String): Boolean = references().any { it.configPath?.let { configPath -> FileUtil.pathsEqual(configPath, path) } ?: false }
+
+    fun invalidate() {
+        references = null
+    }
+
+    /**
+     * @return system independent expanded path, null if the macros can't be expanded
+     */
+    private fun expand(configuration: RunConfigurationBase<*>?, path: String): String? {
+        return try {
+            FileUtil.toSystemIndependentName(MirrordPathMacros.expandConfigPath(project, configuration, path))
+        } catch (e: MirrordError) {
+            null
+        }
+    }
+
+    override fun dispose() {}
+}
@@ -0,0 +1,105 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.node.JsonNodeFactory
+import com.fasterxml.jackson.databind.node.ObjectNode
+
+/**
+ * Starting points for new mirrord config files, one for each common scenario.
+ */
+enum class MirrordConfigTemplate(val presentableName: String, val description: String) {
+    MIRROR(
+        "Mirror traffic",
+        "Receives a copy of the incoming traffic of the target, the target keeps handling the requests."
+    ) {
+        override fun build(root: ObjectNode) {
+            root.putObject("feature").apply {
+                putObject("network").apply {
+                    put("incoming", "mirror")
+                    put("outgoing", true)
+                }
+                put("fs", "read")
+                put("env", true)
+            }
+        }
+    },
+    STEAL_WITH_HTTP_FILTER(
+        "Steal HTTP requests with a header filter",
+        "Takes over only the HTTP requests with a matching header, other requests are handled by the target."
+    ) {
+        override fun build(root: ObjectNode) {
+            val user = System.getProperty("user.name") ?: "me"
+            root.putObject("feature").apply {
+                putObject("network").apply {
+                    putObject("incoming").apply {
+                        put("mode", "steal")
+                        putObject("http_filter").put("header_filter", "x-mirrord-user: $user")
+                    }
+                    put("outgoing", true)
+                }
+                put("fs", "read")
+                put("env", true)
+            }
+        }
+    },
+    TARGETLESS(
+        "Targetless",
+        "Runs without a target, the application can reach the cluster but does not receive any traffic."
+    ) {
+        override fun build(root: ObjectNode) {
+            root.put("target", "targetless")
+            root.putObject("feature").apply {
+                putObject("network").apply {
+                    put("incoming", "off")
+                    put("outgoing", true)
+                }
+                put("fs", "local")
+                put("env", false)
+            }
+        }
+    },
+    LOCAL_FS_REMOTE_ENV(
+        "Local files with the remote environment",
+        "Reads all files locally, while the environment variables come from the target."
+    ) {
+        override fun build(root: ObjectNode) {
+            root.putObject("feature").apply {
+                putObject("network").apply {
+                    put("incoming", "mirror")
+                    put("outgoing", true)
+                }
+                put("fs", "local")
+                put("env", true)
+            }
+        }
+    },
+    OPERATOR_COPY_TARGET(
+        "Copy the target (mirrord for Teams)",
+        "Steals the traffic of a copy of the target created by the mirrord operator, the original target is not affected."
+    ) {
+        override fun build(root: ObjectNode) {
+            root.put("operator", true)
+            root.putObject("feature").apply {
+                putObject("network").apply {
+                    put("incoming", "steal")
+                    put("outgoing", true)
+                }
+                put("fs", "read")
+                put("env", true)
+                putObject("copy_target").put("scale_down", false)
+            }
+        }
+    };
+
+    protected abstract fun build(root: ObjectNode)
+
+    /**
+     * @return content of the new config file in the given format
+     */
+    fun render(format: MirrordConfigFormat): String {
+        val root = JsonNodeFactory.instance.objectNode()
+        build(root)
+        return format.write(root)
+    }
+
+    override fun toString(): String = presentableName
+}
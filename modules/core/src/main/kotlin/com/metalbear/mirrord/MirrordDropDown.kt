@@ -1,7 +1,10 @@
 package com.metalbear.mirrord
 
+import com.intellij.execution.RunManager
+import com.intellij.execution.configurations.RunConfigurationBase
 import com.intellij.ide.BrowserUtil
 import com.intellij.ide.DataManager
+import com.intellij.notification.NotificationType
 import com.intellij.openapi.actionSystem.*
 import com.intellij.openapi.actionSystem.ex.ComboBoxAction
 import com.intellij.openapi.application.WriteAction
@@ -25,15 +28,21 @@ fun VirtualFile.relativePath(project: Project): String {
 
 class MirrordDropDown : ComboBoxAction(), DumbAware {
 
-    private class ShowActiveConfigAction(val config: VirtualFile, project: Project) :
-        AnAction("Active Config: ${config.relativePath(project)}") {
+    /**
+     * @param scope where the config applies, displayed next to the config path
+     */
+    private class ShowActiveConfigAction(val config: VirtualFile, scope: String, project: Project) :
+        AnAction("Active Config ($scope): ${config.relativePath(project)}") {
         override fun actionPerformed(e: AnActionEvent) {
             val service = e.project?.service<MirrordProjectService>() ?: return
             FileEditorManager.getInstance(service.project).openFile(config, true)
         }
     }
 
-    private class SelectActiveConfigAction : AnAction("Select Active Config") {
+    /**
+     * Asks the user to pick one of the indexed config files of the project, nothing happens if the user cancels.
+     */
+    private abstract class PickConfigAction(text: String, private val dialogTitle: String) : AnAction(text) {
         override fun actionPerformed(e: AnActionEvent) {
             val service = e.project?.service<MirrordProjectService>() ?: return
 
@@ -47,14 +56,16 @@ class MirrordDropDown : ComboBoxAction(), DumbAware {
                 .filter { projectLocator.getProjectsForFile(it).contains(service.project) }
                 .associateBy { it.relativePath(service.project) }
 
-            val selection = MirrordConfigDialog(
-                "Change mirrord active configuration",
-                configs.keys.toList().sorted()
-            ).show() ?: return
+            val selection = MirrordConfigDialog(dialogTitle, configs.keys.toList().sorted()).show() ?: return
 
-            service.activeConfig = selection.option?.let { configs[it] }
+            configPicked(service, selection.option?.let { configs[it] })
         }
 
+        /**
+         * @param config null if the user did not pick any config
+         */
+        protected abstract fun configPicked(service: MirrordProjectService, config: VirtualFile?)
+
         override fun update(e: AnActionEvent) {
             e.presentation.isEnabled = e.project?.let { !DumbService.isDumb(it) } ?: false
             super.update(e)
@@ -63,6 +74,30 @@ class MirrordDropDown : ComboBoxAction(), DumbAware {
         override fun getActionUpdateThread() = ActionUpdateThread.BGT
     }
 
+    private class SelectActiveConfigAction : PickConfigAction("Select Active Config", "Change mirrord active configuration") {
+        override fun configPicked(service: MirrordProjectService, config: VirtualFile?) {
+            service.activeConfig = config
+        }
+    }
+
+    /**
+     * Sets the config in the mirrord tab of the run configuration, it's used instead of the active config when the run configuration starts.
+     */
+    private class BindConfigAction(private val configuration: RunConfigurationBase<*>) :
+        PickConfigAction("Bind Config to \"${configuration.name}\"", "Bind mirrord configuration to \"${configuration.name}\"") {
+        override fun configPicked(service: MirrordProjectService, config: VirtualFile?) {
+            service.activeConfigs.bind(configuration, config)
+        }
+    }
+
+    private class UnbindConfigAction(private val configuration: RunConfigurationBase<*>) :
+        AnAction("Unbind Config from \"${configuration.name}\"") {
+        override fun actionPerformed(e: AnActionEvent) {
+            val service = e.project?.service<MirrordProjectService>() ?: return
+            service.activeConfigs.bind(configuration, null)
+        }
+    }
+
     private class SettingsAction : AnAction("Settings") {
         override fun actionPerformed(e: AnActionEvent) {
             val service = e.project?.service<MirrordProjectService>() ?: return
@@ -107,12 +142,60 @@ class MirrordDropDown : ComboBoxAction(), DumbAware {
         }
     }
 
+    /**
+     * Disabled item that explains why there is nothing to select.
+     */
+    private class PlaceholderAction(text: String) : AnAction(text), DumbAware {
+        override fun actionPerformed(e: AnActionEvent) {}
+
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = false
+        }
+
+        override fun getActionUpdateThread() = ActionUpdateThread.BGT
+    }
+
+    /**
+     * Picks the kube context used by the next mirrord runs.
+     * The user's kubeconfig is not modified, the context is passed to the mirrord binary as an override.
+     */
+    private class SelectKubeContextAction(private val context: String, private val kubeConfigCurrent: String?) :
+        ToggleAction(if (context == kubeConfigCurrent) "$context (kubeconfig current)" else context), DumbAware {
+        override fun isSelected(e: AnActionEvent): Boolean {
+            val service = e.project?.service<MirrordProjectService>() ?: return false
+            return (service.kubeContext ?: kubeConfigCurrent) == context
+        }
+
+        override fun setSelected(e: AnActionEvent, state: Boolean) {
+            val service = e.project?.service<MirrordProjectService>() ?: return
+            if (!state) {
+                return
+            }
+
+            service.kubeContext = context.takeUnless { it == kubeConfigCurrent }
+            service.notifier.notifySimple("mirrord will use the `$context` cluster context", NotificationType.INFORMATION)
+        }
+
+        override fun getActionUpdateThread() = ActionUpdateThread.BGT
+    }
+
     private class NavigateToMirrodForTeamsIntroAction : AnAction("Try It Now") {
         override fun actionPerformed(e: AnActionEvent) {
             BrowserUtil.browse(MIRRORD_FOR_TEAMS_URL)
         }
     }
 
+    /**
+     * Opens a tab of the mirrord tool window and refreshes the operator status displayed there.
+     */
+    private class ShowOperatorTabAction(text: String, private val tab: String) : AnAction(text), DumbAware {
+        override fun actionPerformed(e: AnActionEvent) {
+            val service = e.project?.service<MirrordProjectService>() ?: return
+            MirrordToolWindowFactory.showTab(service.project, tab)
+            service.operator.refresh()
+        }
+    }
+
     private class DiscordAction : AnAction("Get Help on Discord") {
         override fun actionPerformed(e: AnActionEvent) {
             BrowserUtil.browse(DISCORD_URL)
@@ -127,12 +210,36 @@ class MirrordDropDown : ComboBoxAction(), DumbAware {
 
         return DefaultActionGroup().apply {
             addSeparator("Configuration")
-            service.activeConfig?.let { add(ShowActiveConfigAction(it, project)) }
+            val selectedConfiguration = RunManager.getInstance(project).selectedConfiguration?.configuration
+            val boundConfig = selectedConfiguration?.let { service.activeConfigs.boundConfig(it) }
+            if (selectedConfiguration != null && boundConfig != null) {
+                add(ShowActiveConfigAction(boundConfig, "bound to \"${selectedConfiguration.name}\"", project))
+            }
+            service.activeConfig?.let {
+                add(ShowActiveConfigAction(it, if (boundConfig != null) "project, overridden" else "project", project))
+            }
             add(SelectActiveConfigAction())
+            (selectedConfiguration as? RunConfigurationBase<*>)?.let {
+                add(BindConfigAction(it))
+                if (boundConfig != null) {
+                    add(UnbindConfigAction(it))
+                }
+            }
             add(SettingsAction())
 
-            if (!MirrordSettingsState.instance.mirrordState.operatorUsed) {
-                addSeparator("mirrord for Teams")
+            addSeparator("Cluster Context")
+            val kubeContexts = service.kubeConfig.cachedContexts()
+            when {
+                kubeContexts == null -> add(PlaceholderAction("Loading Contexts from Kubeconfig..."))
+                kubeContexts.contexts.isEmpty() -> add(PlaceholderAction("No Contexts Found in Kubeconfig"))
+                else -> kubeContexts.contexts.forEach { add(SelectKubeContextAction(it, kubeContexts.current)) }
+            }
+
+            addSeparator("mirrord for Teams")
+            if (MirrordSettingsState.instance.mirrordState.operatorUsed) {
+                add(ShowOperatorTabAction("Operator Status", MirrordOperatorStatusPanel.TITLE))
+                add(ShowOperatorTabAction("Operator Sessions", MirrordOperatorSessionsPanel.TITLE))
+            } else {
                 add(NavigateToMirrodForTeamsIntroAction())
             }
 
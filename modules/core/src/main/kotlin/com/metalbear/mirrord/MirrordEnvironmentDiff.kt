@@ -0,0 +1,52 @@
+package com.metalbear.mirrord
+
+/**
+ * Changes mirrord makes to the environment of the local process.
+ *
+ * @param entries changed variables, sorted by name
+ */
+class MirrordEnvironmentDiff(val entries: List<Entry>) {
+    enum class Change(val presentableName: String) {
+        /**
+         * Variable from the remote pod that was not set locally.
+         */
+        ADDED("Added"),
+
+        /**
+         * Local value replaced with the value from the remote pod.
+         */
+        OVERRIDDEN("Overridden"),
+
+        /**
+         * Variable removed from the local environment.
+         */
+        UNSET("Unset")
+    }
+
+    /**
+     * @param localValue value the process gets without mirrord, null if the variable was not set
+     * @param remoteValue value set by mirrord, null if the variable was unset
+     */
+    data class Entry(val name: String, val change: Change, val localValue: String?, val remoteValue: String?)
+
+    companion object {
+        /**
+         * @param localEnv environment of the process without mirrord, including the inherited environment of the IDE
+         * @param execution result of `mirrord ext`, as it is applied to the process
+         */
+        fun compute(localEnv: Map<String, String>?, execution: MirrordExecution): MirrordEnvironmentDiff {
+            val local = localEnv.orEmpty()
+
+            val changed = execution.environment.mapNotNull { (name, value) ->
+                when (val localValue = local[name]) {
+                    null -> Entry(name, Change.ADDED, null, value)
+                    value -> null
+                    else -> Entry(name, Change.OVERRIDDEN, localValue, value)
+                }
+            }
+            val unset = execution.envToUnset.orEmpty().map { Entry(it, Change.UNSET, local[it], null) }
+
+            return MirrordEnvironmentDiff((changed + unset).sortedBy { it.name })
+        }
+    }
+}
@@ -0,0 +1,166 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.CommonProgramRunConfigurationParameters
+import com.intellij.execution.RunManager
+import com.intellij.execution.RunnerAndConfigurationSettings
+import com.intellij.notification.NotificationType
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.components.service
+import com.intellij.openapi.fileChooser.FileChooserFactory
+import com.intellij.openapi.fileChooser.FileSaverDescriptor
+import com.intellij.openapi.ide.CopyPasteManager
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.popup.JBPopupFactory
+import com.intellij.openapi.vfs.LocalFileSystem
+import com.intellij.ui.SimpleListCellRenderer
+import java.awt.datatransfer.StringSelection
+import java.nio.file.Path
+
+/**
+ * Names of config keys and environment variables that look like secrets.
+ * Their values are removed from issue reports, and they are skipped when the environment is exported.
+ */
+val SECRET_NAME_REGEX = Regex("(?i)(token|secret|password|passwd|credential|auth|api[_-]?key|private[_-]?key|cert)")
+
+/**
+ * Variables used by mirrord to inject itself into the process. They are valid only for a single launch.
+ */
+private val MIRRORD_INTERNAL_REGEX = Regex("^(MIRRORD_.*|LD_PRELOAD|DYLD_INSERT_LIBRARIES)$")
+
+/**
+ * Exports the environment from a [MirrordExecution], so that it can be used outside of a single launch.
+ *
+ * @param variables variables to set, without the secret-looking and mirrord internal ones
+ * @param unset variables to remove, from [MirrordExecution.envToUnset]
+ * @param skippedSecrets names of the variables skipped because they look like secrets
+ */
+class MirrordEnvironmentExport(
+    val variables: Map<String, String>,
+    val unset: List<String>,
+    val skippedSecrets: List<String>
+) {
+    companion object {
+        fun of(execution: MirrordExecution): MirrordEnvironmentExport {
+            val (secrets, variables) = execution
+                .environment
+                .filterKeys { !MIRRORD_INTERNAL_REGEX.matches(it) }
+                .toSortedMap()
+                .entries
+                .partition { SECRET_NAME_REGEX.containsMatchIn(it.key) }
+
+            return MirrordEnvironmentExport(
+                variables.associate { it.key to it.value },
+                execution.envToUnset.orEmpty().sorted(),
+                secrets.map { it.key }
+            )
+        }
+    }
+
+    /**
+     * `.env` file content. The format has no way to unset a variable, so [unset] is not included.
+     */
+    fun toDotEnv(): String {
+        return variables.entries.joinToString("\n", postfix = "\n") { (name, value) ->
+            val escaped = value
+                .replace("\\", "\\\\")
+                .replace("\"", "\\\"")
+                .replace("\n", "\\n")
+            "$name=\"$escaped\""
+        }
+    }
+
+    /**
+     * Shell script that applies the environment, e.g. `export NAME='value'` and `unset NAME`.
+     */
+    fun toShellExports(): String {
+        val exports = variables.map { (name, value) -> "export $name='${value.replace("'", "'\\''")}'" }
+        val unsets = unset.map { "unset $it" }
+        return (exports + unsets).joinToString("\n", postfix = "\n")
+    }
+
+    /**
+     * Sets the variables in the run configuration and removes the ones from [unset].
+     */
+    fun applyTo(configuration: CommonProgramRunConfigurationParameters) {
+        configuration.envs = configuration.envs.filterKeys { !unset.contains(it) } + variables
+    }
+
+    /**
+     * Short summary for the notification, mentions the skipped secrets.
+     */
+    fun summary(): String {
+        val skipped = if (skippedSecrets.isEmpty()) {
+            ""
+        } else {
+            " Skipped variables that look like secrets: ${skippedSecrets.joinToString(", ")}."
+        }
+        return "Exported ${variables.size} environment variables.$skipped"
+    }
+}
+
+/**
+ * Actions of the environment panel that export the environment of a session.
+ */
+object MirrordEnvironmentExportActions {
+    fun saveDotEnv(project: Project, export: MirrordEnvironmentExport) {
+        val descriptor = FileSaverDescriptor("Save Environment", "Save the environment as a .env file", "env")
+        val wrapper = FileChooserFactory
+            .getInstance()
+            .createSaveFileDialog(descriptor, project)
+            .save(project.basePath?.let { Path.of(it) }, ".env")
+            ?: return
+
+        wrapper.file.writeText(export.toDotEnv())
+        LocalFileSystem.getInstance().refreshAndFindFileByIoFile(wrapper.file)
+
+        project
+            .service<MirrordProjectService>()
+            .notifier
+            .notification(export.summary(), NotificationType.INFORMATION)
+            .withOpenPath(wrapper.file.path)
+            .fire()
+    }
+
+    fun copyShellExports(project: Project, export: MirrordEnvironmentExport) {
+        CopyPasteManager.getInstance().setContents(StringSelection(export.toShellExports()))
+        project.service<MirrordProjectService>().notifier.notifySimple(export.summary(), NotificationType.INFORMATION)
+    }
+
+    /**
+     * Lets the user pick a run configuration and writes the environment into it.
+     */
+    fun exportToRunConfiguration(project: Project, export: MirrordEnvironmentExport, e: AnActionEvent) {
+        val configurations = RunManager
+            .getInstance(project)
+            .allSettings
+            .filter { it.configuration is CommonProgramRunConfigurationParameters }
+
+        if (configurations.isEmpty()) {
+            project.service<MirrordProjectService>().notifier.notifySimple(
+                "There are no run configurations with environment variables in this project",
+                NotificationType.WARNING
+            )
+            return
+        }
+
+        JBPopupFactory
+            .getInstance()
+            .createPopupChooserBuilder(configurations)
+            .setTitle("Export Environment to Run Configuration")
+            .setRenderer(
+                SimpleListCellRenderer.create<RunnerAndConfigurationSettings> { label, value, _ ->
+                    label.text = value.name
+                    label.icon = value.configuration.icon
+                }
+            )
+            .setItemChosenCallback { settings ->
+                export.applyTo(settings.configuration as CommonProgramRunConfigurationParameters)
+                project.service<MirrordProjectService>().notifier.notifySimple(
+                    "${export.summary()} Run configuration \"${settings.name}\" was updated.",
+                    NotificationType.INFORMATION
+                )
+            }
+            .createPopup()
+            .showInBestPositionFor(e.dataContext)
+    }
+}
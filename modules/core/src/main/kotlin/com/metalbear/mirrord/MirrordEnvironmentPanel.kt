@@ -0,0 +1,191 @@
+package com.metalbear.mirrord
+
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.actionSystem.ActionManager
+import com.intellij.openapi.actionSystem.AnAction
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.actionSystem.DefaultActionGroup
+import com.intellij.openapi.actionSystem.Separator
+import com.intellij.openapi.actionSystem.ToggleAction
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.ComboBox
+import com.intellij.openapi.ui.SimpleToolWindowPanel
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.SimpleListCellRenderer
+import com.intellij.ui.components.JBLabel
+import com.intellij.ui.table.TableView
+import com.intellij.util.Alarm
+import com.intellij.util.ui.ColumnInfo
+import com.intellij.util.ui.JBUI
+import com.intellij.util.ui.ListTableModel
+import com.intellij.util.ui.UIUtil
+import java.awt.BorderLayout
+import javax.swing.DefaultComboBoxModel
+import javax.swing.Icon
+import javax.swing.JPanel
+
+private const val MASKED_VALUE = "••••••"
+
+/**
+ * Displays the changes mirrord made to the environment of the process in the selected session.
+ * Values are masked unless the user chooses to show them.
+ */
+class MirrordEnvironmentPanel(project: Project, parentDisposable: Disposable) : SimpleToolWindowPanel(true, true) {
+    private val manager = project.service<MirrordProjectService>().sessions
+
+    private var showValues = false
+
+    private val sessionModel = DefaultComboBoxModel<MirrordSession>()
+
+    private val sessionBox = ComboBox(sessionModel).apply {
+        renderer = SimpleListCellRenderer.create("") { session ->
+            "Session #${session.id}  ${session.target ?: "target from config or targetless"}"
+        }
+        addActionListener { updateTable() }
+    }
+
+    private val columns: Array<ColumnInfo<MirrordEnvironmentDiff.Entry, String>> = arrayOf(
+        object : ColumnInfo<MirrordEnvironmentDiff.Entry, String>("Variable") {
+            override fun valueOf(item: MirrordEnvironmentDiff.Entry): String = item.name
+        },
+        object : ColumnInfo<MirrordEnvironmentDiff.Entry, String>("Change") {
+            override fun valueOf(item: MirrordEnvironmentDiff.Entry): String = item.change.presentableName
+        },
+        object : ColumnInfo<MirrordEnvironmentDiff.Entry, String>("Local Value") {
+            override fun valueOf(item: MirrordEnvironmentDiff.Entry): String = mask(item.localValue)
+        },
+        object : ColumnInfo<MirrordEnvironmentDiff.Entry, String>("Remote Value") {
+            override fun valueOf(item: MirrordEnvironmentDiff.Entry): String = mask(item.remoteValue)
+        }
+    )
+
+    private val tableModel = ListTableModel<MirrordEnvironmentDiff.Entry>(*columns)
+
+    private val table = TableView(tableModel).apply {
+        emptyText.text = "No environment changes to display"
+    }
+
+    /**
+     * Batches updates, the session manager reports every task of a starting session.
+     */
+    private val updateAlarm = Alarm(Alarm.ThreadToUse.SWING_THREAD, parentDisposable)
+
+    private inner class ShowValuesAction : ToggleAction("Show Values", null, AllIcons.Actions.Show), DumbAware {
+        override fun isSelected(e: AnActionEvent): Boolean = showValues
+
+        override fun setSelected(e: AnActionEvent, state: Boolean) {
+            showValues = state
+            tableModel.fireTableDataChanged()
+        }
+    }
+
+    /**
+     * Exports the environment of the selected session.
+     */
+    private inner class ExportAction(
+        text: String,
+        icon: Icon,
+        private val export: (MirrordEnvironmentExport, AnActionEvent) -> Unit
+    ) : AnAction(text, null, icon), DumbAware {
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = selectedExecution() != null
+        }
+
+        override fun actionPerformed(e: AnActionEvent) {
+            val execution = selectedExecution() ?: return
+            export(MirrordEnvironmentExport.of(execution), e)
+        }
+    }
+
+    init {
+        val actions = DefaultActionGroup(
+            ShowValuesAction(),
+            Separator.getInstance(),
+            ExportAction("Save as .env File", AllIcons.Actions.MenuSaveall) { export, _ ->
+                MirrordEnvironmentExportActions.saveDotEnv(project, export)
+            },
+            ExportAction("Copy as Shell Exports", AllIcons.Actions.Copy) { export, _ ->
+                MirrordEnvironmentExportActions.copyShellExports(project, export)
+            },
+            ExportAction("Export to Run Configuration...", AllIcons.ToolbarDecorator.Export) { export, e ->
+                MirrordEnvironmentExportActions.exportToRunConfiguration(project, export, e)
+            }
+        )
+        val toolbar = ActionManager
+            .getInstance()
+            .createActionToolbar("MirrordEnvironment", actions, true)
+        toolbar.targetComponent = this
+        setToolbar(toolbar.component)
+
+        val header = JPanel(BorderLayout(JBUI.scale(5), 0)).apply {
+            border = JBUI.Borders.empty(5)
+            add(JBLabel("Session:"), BorderLayout.WEST)
+            add(sessionBox, BorderLayout.CENTER)
+        }
+        val note = JBLabel("Local values include the environment inherited from the IDE, unless the run configuration does not pass it.").apply {
+            componentStyle = UIUtil.ComponentStyle.SMALL
+            fontColor = UIUtil.FontColor.BRIGHTER
+            border = JBUI.Borders.empty(5)
+        }
+        setContent(
+            JPanel(BorderLayout()).apply {
+                add(header, BorderLayout.NORTH)
+                add(ScrollPaneFactory.createScrollPane(table), BorderLayout.CENTER)
+                add(note, BorderLayout.SOUTH)
+            }
+        )
+
+        manager.addListener({ scheduleUpdate() }, parentDisposable)
+        update()
+    }
+
+    private fun mask(value: String?): String = when {
+        value == null -> ""
+        showValues -> value
+        else -> MASKED_VALUE
+    }
+
+    private fun scheduleUpdate() {
+        if (updateAlarm.isDisposed) {
+            return
+        }
+
+        updateAlarm.cancelAllRequests()
+        updateAlarm.addRequest({ update() }, 100, ModalityState.any())
+    }
+
+    /**
+     * Synchronizes the session list with the sessions that have the environment recorded.
+     * Keeps the selected session if it is still available, otherwise selects the latest one.
+     */
+    private fun update() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+
+        val sessions = manager.sessions.filter { it.environment != null }
+        val current = (0 until sessionModel.size).map { sessionModel.getElementAt(it) }
+        if (sessions == current) {
+            return
+        }
+
+        val selected = sessionBox.selectedItem as? MirrordSession
+        val wasLatest = selected == null || selected == current.firstOrNull()
+
+        sessionModel.removeAllElements()
+        sessionModel.addAll(sessions)
+        sessionModel.selectedItem = selected?.takeIf { !wasLatest && sessions.contains(it) } ?: sessions.firstOrNull()
+
+        updateTable()
+    }
+
+    private fun selectedExecution(): MirrordExecution? = (sessionBox.selectedItem as? MirrordSession)?.execution
+
+    private fun updateTable() {
+        val session = sessionBox.selectedItem as? MirrordSession
+        tableModel.items = session?.environment?.entries.orEmpty()
+    }
+}
@@ -2,26 +2,35 @@ package com.metalbear.mirrord
 
 import com.google.gson.Gson
 import com.intellij.execution.ExecutionException
-import com.intellij.notification.NotificationType
 import com.intellij.openapi.components.service
 import com.intellij.openapi.project.Project
 
-open class MirrordError(private val richMessage: String, private val help: String?, override val cause: Throwable?) : ExecutionException(cause) {
+open class MirrordError(val richMessage: String, val help: String?, override val cause: Throwable?) : ExecutionException(cause) {
     override val message: String = "mirrord failed"
 
+    /**
+     * Full diagnostic reported by the mirrord binary, null if the error did not come from the binary.
+     */
+    var diagnostic: Error? = null
+        private set
+
+    /**
+     * Config file that was used when the error occurred, null if not known.
+     * Included (sanitized) in the issue report.
+     */
+    var configPath: String? = null
+
     companion object {
         fun fromStdErr(processStdErr: String): MirrordError {
-            val info = try {
+            return try {
                 val trimmedError = processStdErr.removePrefix("Error: ")
                 val gson = Gson()
                 val error = gson.fromJson(trimmedError, Error::class.java)
-                Pair(error.message, error.help)
+                MirrordError(error.message, error.help, null).apply { diagnostic = error }
             } catch (e: Throwable) {
                 MirrordLogger.logger.debug("failed to deserialize stderr: $processStdErr", e)
-                Pair(processStdErr, null)
+                MirrordError(processStdErr, null, null)
             }
-
-            return MirrordError(info.first, info.second, null)
         }
     }
 
@@ -36,9 +45,8 @@ open class MirrordError(private val richMessage: String, private val help: Strin
             .service<MirrordProjectService>()
             .notifier
 
-        notifier.notifyRichError(richMessage)
-        help?.let {
-            notifier.notifySimple(it, NotificationType.INFORMATION)
+        notifier.notifyRichError(richMessage, help) {
+            MirrordErrorDialog(project, this).show()
         }
     }
 }
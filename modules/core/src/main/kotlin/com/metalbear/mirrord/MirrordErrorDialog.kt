@@ -0,0 +1,202 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.JsonNode
+import com.fasterxml.jackson.databind.node.ArrayNode
+import com.fasterxml.jackson.databind.node.ObjectNode
+import com.fasterxml.jackson.databind.node.TextNode
+import com.intellij.ide.BrowserUtil
+import com.intellij.openapi.application.ApplicationInfo
+import com.intellij.openapi.components.service
+import com.intellij.openapi.ide.CopyPasteManager
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.DialogWrapper
+import com.intellij.openapi.util.SystemInfo
+import com.intellij.ui.components.JBScrollPane
+import com.intellij.ui.components.JBTextArea
+import com.intellij.util.ui.JBFont
+import java.awt.Dimension
+import java.awt.Font
+import java.awt.datatransfer.StringSelection
+import java.awt.event.ActionEvent
+import java.io.File
+import java.net.URLEncoder
+import javax.swing.AbstractAction
+import javax.swing.Action
+import javax.swing.JComponent
+
+/**
+ * Sections of the config where every value is redacted, whatever the key is.
+ * Environment overrides and HTTP header filters often contain credentials.
+ */
+private val REDACTED_SECTIONS = setOf("env", "override", "header_filter")
+
+private const val REDACTED = "<redacted>"
+
+/**
+ * GitHub limits the length of the new issue url, longer reports are truncated.
+ */
+private const val MAX_REPORT_LENGTH = 6000
+
+/**
+ * Displays the whole diagnostic of a [MirrordError], with the option to copy it or to report an issue on GitHub.
+ */
+class MirrordErrorDialog(project: Project, private val error: MirrordError) : DialogWrapper(project, true) {
+    init {
+        title = "mirrord Error"
+        setOKButtonText("Close")
+        init()
+    }
+
+    override fun createCenterPanel(): JComponent {
+        val text = JBTextArea(details()).apply {
+            isEditable = false
+            lineWrap = true
+            wrapStyleWord = true
+            font = JBFont.create(Font(Font.MONOSPACED, Font.PLAIN, font.size))
+        }
+
+        return JBScrollPane(text).apply {
+            preferredSize = Dimension(650, 400)
+        }
+    }
+
+    override fun createActions(): Array<Action> = arrayOf(okAction)
+
+    override fun createLeftSideActions(): Array<Action> = arrayOf(
+        object : AbstractAction("Copy") {
+            override fun actionPerformed(e: ActionEvent) {
+                CopyPasteManager.getInstance().setContents(StringSelection(report()))
+            }
+        },
+        object : AbstractAction("Report Issue") {
+            override fun actionPerformed(e: ActionEvent) {
+                val title = URLEncoder.encode(error.richMessage.lineSequence().first().take(100), Charsets.UTF_8)
+                val body = URLEncoder.encode(report().take(MAX_REPORT_LENGTH), Charsets.UTF_8)
+                BrowserUtil.browse("$GITHUB_URL/issues/new?labels=bug&title=$title&body=$body")
+            }
+        }
+    )
+
+    /**
+     * The whole diagnostic chain of the error.
+     */
+    private fun details(): String {
+        val builder = StringBuilder()
+        val diagnostic = error.diagnostic
+
+        diagnostic?.severity?.let { builder.append("[$it] ") }
+        builder.appendLine(error.richMessage)
+
+        // The diagnostic comes from the mirrord binary and any of the fields can be missing.
+        val causes: List<String>? = diagnostic?.causes
+        causes?.forEach { builder.appendLine("  caused by: $it") }
+
+        val labels: List<String>? = diagnostic?.labels
+        if (!labels.isNullOrEmpty()) {
+            builder.appendLine().appendLine("Labels:")
+            labels.forEach { builder.appendLine("  $it") }
+        }
+
+        val related: List<String>? = diagnostic?.related
+        if (!related.isNullOrEmpty()) {
+            builder.appendLine().appendLine("Related:")
+            related.forEach { builder.appendLine("  $it") }
+        }
+
+        error.help?.let { builder.appendLine().appendLine("Help: $it") }
+
+        generateSequence(error.cause) { it.cause }.forEach {
+            builder.appendLine().appendLine("Caused by: $it")
+        }
+
+        return builder.toString()
+    }
+
+    /**
+     * Error details with the information about the environment, in markdown.
+     */
+    private fun report(): String {
+        val binaryVersion = service<MirrordBinaryManager>().usedVersion ?: "unknown"
+        val config = error.configPath?.let { sanitizedConfig(it) }
+
+        return buildString {
+            appendLine("### Error")
+            appendLine("```")
+            appendLine(details().trimEnd())
+            appendLine("```")
+            appendLine()
+            appendLine("### Environment")
+            appendLine("- plugin version: ${VERSION ?: "unknown"}")
+            appendLine("- mirrord binary version: $binaryVersion")
+            appendLine("- IDE: ${ApplicationInfo.getInstance().fullApplicationName}")
+            appendLine("- OS: ${SystemInfo.getOsNameAndVersion()}")
+            appendLine()
+            appendLine("### Config")
+            if (config == null) {
+                appendLine("No config file was used.")
+            } else {
+                appendLine("```")
+                appendLine(config.trimEnd())
+                appendLine("```")
+            }
+        }
+    }
+
+    /**
+     * Reads the config for the issue report, see [sanitizeConfig].
+     */
+    private fun sanitizedConfig(path: String): String {
+        val content = try {
+            File(path).readText()
+        } catch (e: Exception) {
+            MirrordLogger.logger.debug("failed to read config $path for the issue report", e)
+            return "Failed to read the config file."
+        }
+
+        return sanitizeConfig(path, content)
+    }
+
+    companion object {
+        /**
+         * Removes the values of the keys that look like secrets, and all the values in [REDACTED_SECTIONS].
+         * A config that cannot be parsed is not included at all, so that no secret leaks through the raw text.
+         *
+         * @param path path of the config, used to pick the format
+         * @return the sanitized config, or a note on why it is not included
+         */
+        fun sanitizeConfig(path: String, content: String): String {
+            val format = MirrordConfigFormat.ofPath(path) ?: return "The config file is not a json, yaml or toml file."
+            val root = try {
+                format.parse(path, content)
+            } catch (e: InvalidConfigException) {
+                return "The config file could not be parsed, it is not included."
+            }
+
+            redact(root, false)
+            return format.write(root)
+        }
+
+        /**
+         * Replaces the secret values in place. Booleans and nulls are kept, they cannot hold a secret.
+         *
+         * @param redactAll whether all values in this node are redacted, set inside secret keys and [REDACTED_SECTIONS]
+         */
+        private fun redact(node: JsonNode, redactAll: Boolean): JsonNode {
+            return when {
+                node is ObjectNode -> node.apply {
+                    fieldNames().asSequence().toList().forEach { key ->
+                        val secret = redactAll || key in REDACTED_SECTIONS || SECRET_NAME_REGEX.containsMatchIn(key)
+                        replace(key, redact(get(key), secret))
+                    }
+                }
+                node is ArrayNode -> node.apply {
+                    for (i in 0 until size()) {
+                        set(i, redact(get(i), redactAll))
+                    }
+                }
+                redactAll && (node.isTextual || node.isNumber) -> TextNode(REDACTED)
+                else -> node
+            }
+        }
+    }
+}
@@ -1,13 +1,23 @@
 package com.metalbear.mirrord
 
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.progress.EmptyProgressIndicator
+import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.progress.ProgressIndicator
+import com.intellij.openapi.ui.ComboBox
 import com.intellij.openapi.ui.DialogBuilder
 import com.intellij.openapi.ui.DialogWrapper
+import com.intellij.ui.AnimatedIcon
 import com.intellij.ui.JBColor
 import com.intellij.ui.components.JBBox
 import com.intellij.ui.components.JBCheckBox
-import com.intellij.ui.components.JBList
 import com.intellij.ui.components.JBScrollPane
+import com.intellij.ui.table.TableView
+import com.intellij.util.ui.ColumnInfo
 import com.intellij.util.ui.JBUI
+import com.intellij.util.ui.ListTableModel
 import java.awt.Dimension
 import java.awt.event.*
 import javax.swing.*
@@ -18,30 +28,58 @@ object MirrordExecDialog {
     private const val dialogHeading: String = "mirrord"
     private const val targetLabel = "Select Target"
     private const val searchPlaceHolder = "Filter targets..."
+    private const val defaultContainer = "(default)"
+    private const val loadingText = "Loading targets..."
+    private const val failedText = "Failed to list targets"
 
     /**
      * Label that's used to select targetless mode
      */
     const val targetlessTargetName = "No Target (\"targetless\")"
 
+    /**
+     * Columns of the targets table. The `null` row represents the targetless mode.
+     *
+     * @param rememberedTarget placeholder row of the last chosen target, displayed before the targets are listed
+     */
+    private fun columns(rememberedTarget: MirrordTarget?): Array<ColumnInfo<MirrordTarget?, String>> = arrayOf(
+        object : ColumnInfo<MirrordTarget?, String>("Target") {
+            override fun valueOf(item: MirrordTarget?): String = item?.path ?: targetlessTargetName
+        },
+        object : ColumnInfo<MirrordTarget?, String>("Kind") {
+            override fun valueOf(item: MirrordTarget?): String = item?.kind.orEmpty()
+        },
+        object : ColumnInfo<MirrordTarget?, String>("Namespace") {
+            override fun valueOf(item: MirrordTarget?): String = item?.namespace.orEmpty()
+        },
+        object : ColumnInfo<MirrordTarget?, String>("Containers") {
+            override fun valueOf(item: MirrordTarget?): String = item?.containers?.joinToString(", ").orEmpty()
+        },
+        object : ColumnInfo<MirrordTarget?, String>("Available") {
+            override fun valueOf(item: MirrordTarget?): String = when {
+                item == null || item === rememberedTarget -> ""
+                item.available -> "yes"
+                else -> "no"
+            }
+        }
+    )
+
     /**
      * Manages the state of targets list in the dialog. Keeps all the filters in one place.
      */
-    private class TargetsState(private var availableTargets: List<String>) {
+    private class TargetsState(var availableTargets: List<MirrordTarget>, private val lastChosen: String?) {
         /**
-         * Whether to show pods.
+         * Visibility of target kinds, kinds that are not in the map are visible.
          */
-        var pods = MirrordSettingsState.instance.mirrordState.showPodsInSelection ?: true
+        val kindsVisibility: MutableMap<String, Boolean> = HashMap(MirrordSettingsState.instance.mirrordState.targetKindsVisibility)
 
         /**
-         * Whether to show deployments.
+         * Kinds of the available targets, each one gets a filter.
          */
-        var deployments = MirrordSettingsState.instance.mirrordState.showDeploymentsInSelection ?: true
+        val kinds: List<String>
+            get() = availableTargets.map { it.kind }.distinct().sorted()
 
-        /**
-         * Whether to show rollouts.
-         */
-        var rollouts = MirrordSettingsState.instance.mirrordState.showRolloutsInSelection ?: false
+        fun isVisible(kind: String): Boolean = kindsVisibility[kind] ?: true
 
         /**
          * Show only targets containing this phrase.
@@ -49,42 +87,205 @@ object MirrordExecDialog {
         var searchPhrase = ""
 
         /**
-         * Filtered and sorted targets, targetless option at the bottom, last chosen target at the top.
+         * Filtered and sorted targets, targetless option (`null`) at the bottom, last chosen target at the top.
          */
-        val targets: List<String>
+        val targets: List<MirrordTarget?>
             get() {
                 return this.availableTargets
-                    .filter {
-                        (this.pods && it.startsWith("pod/")) ||
-                            (this.deployments && it.startsWith("deployment/")) ||
-                            (this.rollouts && it.startsWith("rollout/"))
-                    }
-                    .filter { it.contains(this.searchPhrase) }
-                    .toMutableList()
+                    .filter { isVisible(it.kind) }
+                    .filter { it.path.contains(this.searchPhrase) }
+                    .sortedBy { it.path }
+                    .toMutableList<MirrordTarget?>()
                     .apply {
-                        sort()
-                        MirrordSettingsState.instance.mirrordState.lastChosenTarget?.let {
-                            val idx = this.indexOf(it)
+                        lastChosen?.let { last ->
+                            val idx = this.indexOfFirst { it?.matches(last) ?: false }
                             if (idx != -1) {
-                                this.removeAt(idx)
-                                this.add(0, it)
+                                this.add(0, this.removeAt(idx))
                             }
                         }
-                        add(targetlessTargetName)
+                        add(null)
                     }
                     .toList()
             }
     }
 
     /**
-     * Shows a target selection dialog.
+     * Label of the filter for the given target kind, for example `Pods` or `StatefulSets`.
+     */
+    private fun kindLabel(kind: String): String {
+        val name = when (kind) {
+            "statefulset" -> "StatefulSet"
+            "cronjob" -> "CronJob"
+            "replicaset" -> "ReplicaSet"
+            else -> kind.replaceFirstChar { it.uppercaseChar() }
+        }
+        return "${name}s"
+    }
+
+    /**
+     * Target selected by the user.
      *
-     * @return a target selected from the given list, targetlessTargetName constant if user selected targetless, null if the user cancelled
+     * @param target target path, or targetlessTargetName constant if user selected targetless
+     * @param namespace namespace the target was listed in, null if it was listed in the namespace from the config
+     * @param remember whether the user wants to always use this target for the run configuration
      */
-    fun selectTargetDialog(availableTargets: List<String>): String? {
-        val targetsState = TargetsState(availableTargets)
+    class Selection(val target: String, val namespace: String?, val remember: Boolean = false)
+
+    /**
+     * Shows a target selection dialog. The dialog is displayed immediately and the targets are listed in the background.
+     * Until the first listing finishes, the user can pick the last chosen target or the targetless mode.
+     * The dialog can't be confirmed while the targets are listed and no row is selected.
+     *
+     * @param listTargets lists targets in the given namespace (null for the namespace from the config),
+     * returns null if the listing failed. Called on a background thread. The second argument is set when the user
+     * requested a refresh, and cached results should not be used.
+     * @param lastChosen target the user chose last time for the run configuration, offered before the targets are listed
+     * @param lastChosenNamespace namespace [lastChosen] was listed in, the targets are first listed in this namespace
+     * @param configurationName name of the run configuration, if given the user can choose to always use the selected
+     * target for it
+     * @return the selection, null if the user cancelled
+     */
+    fun selectTargetDialog(
+        lastChosen: String?,
+        lastChosenNamespace: String?,
+        configurationName: String?,
+        listTargets: (String?, Boolean, ProgressIndicator) -> MirrordTargetListing?
+    ): Selection? {
+        val rememberedTarget = lastChosen
+            ?.let { MirrordTarget.parsePath(it) }
+            ?.let { (kind, name, container) -> MirrordTarget(kind, name, null, listOfNotNull(container), true) }
+        val targetsState = TargetsState(listOfNotNull(rememberedTarget), lastChosen)
+
+        val targetsModel = ListTableModel(columns(rememberedTarget), targetsState.targets)
+        val jbTargets = TableView(targetsModel).apply {
+            setSelectionMode(ListSelectionModel.SINGLE_SELECTION)
+        }
+        val refreshTargets = {
+            // Keep the selection, so that the listing finishing in the background does not reset it.
+            val selectedPath = jbTargets.selectedObject?.path
+            targetsModel.items = targetsState.targets
+            targetsModel.items.find { it != null && it.path == selectedPath }?.let { jbTargets.setSelection(listOf(it)) }
+        }
+
+        // Filled with a checkbox for each target kind present in the listing.
+        val kindFilters = JBBox.createHorizontalBox().apply {
+            alignmentX = JBBox.LEFT_ALIGNMENT
+        }
+        val updateKindFilters = {
+            kindFilters.removeAll()
+            targetsState.kinds.forEach { kind ->
+                kindFilters.add(
+                    JBCheckBox(kindLabel(kind), targetsState.isVisible(kind)).apply {
+                        this.addActionListener {
+                            targetsState.kindsVisibility[kind] = this.isSelected
+                            refreshTargets()
+                        }
+                    }
+                )
+                kindFilters.add(Box.createRigidArea(Dimension(10, 0)))
+            }
+            kindFilters.revalidate()
+            kindFilters.repaint()
+        }
+        updateKindFilters()
+
+        val statusLabel = JLabel()
+        val refreshButton = JButton(AllIcons.Actions.Refresh).apply {
+            toolTipText = "Refresh targets"
+        }
+        val namespaceBox = ComboBox<String>().apply {
+            isEnabled = false
+        }
+
+        // Namespace of the currently displayed targets.
+        var listedNamespace: String? = null
+        // Namespace override the displayed targets were listed with, null for the namespace from the config.
+        // The placeholder of the last chosen target belongs to its namespace.
+        var requestedNamespace: String? = lastChosenNamespace
+        // Set when the namespace box is updated with the listing results, so that it does not trigger another listing.
+        var updatingNamespaces = false
+        // Cancels the listing that is currently in progress.
+        var loadingIndicator: ProgressIndicator? = null
+
+        val dialog = DialogBuilder()
+        // The targetless row is `null`, so the selected row is checked instead of the selected object.
+        val updateOkAction = {
+            dialog.setOkActionEnabled(loadingIndicator == null || jbTargets.selectedRow != -1)
+        }
+
+        val loadTargets = { namespace: String?, refresh: Boolean ->
+            loadingIndicator?.cancel()
+            val indicator = EmptyProgressIndicator()
+            loadingIndicator = indicator
+            updateOkAction()
+
+            statusLabel.icon = AnimatedIcon.Default.INSTANCE
+            statusLabel.text = loadingText
+
+            ApplicationManager.getApplication().executeOnPooledThread {
+                val listing = try {
+                    listTargets(namespace, refresh, indicator)
+                } catch (e: ProcessCanceledException) {
+                    null
+                }
+
+                ApplicationManager.getApplication().invokeLater({
+                    if (indicator.isCanceled || loadingIndicator !== indicator) {
+                        return@invokeLater
+                    }
+                    loadingIndicator = null
+                    statusLabel.icon = null
+                    updateOkAction()
+
+                    updatingNamespaces = true
+                    if (listing == null) {
+                        // Listing failed (the user was already notified), go back to the previous namespace.
+                        statusLabel.text = failedText
+                        namespaceBox.selectedItem = listedNamespace
+                    } else {
+                        statusLabel.text = ""
+                        listedNamespace = listing.currentNamespace
+                        requestedNamespace = namespace
+
+                        val namespaces = (listing.namespaces + listOfNotNull(listing.currentNamespace)).distinct().sorted()
+                        namespaceBox.model = DefaultComboBoxModel(namespaces.toTypedArray())
+                        namespaceBox.selectedItem = listing.currentNamespace
+                        namespaceBox.isEnabled = namespaces.size > 1
+
+                        targetsState.availableTargets = listing.targets
+                        updateKindFilters()
+                        refreshTargets()
+                    }
+                    updatingNamespaces = false
+                }, ModalityState.any())
+            }
+        }
+
+        namespaceBox.addActionListener {
+            val namespace = namespaceBox.selectedItem as? String
+            if (updatingNamespaces || namespace == null || namespace == listedNamespace) {
+                return@addActionListener
+            }
+
+            loadTargets(namespace, false)
+        }
+        refreshButton.addActionListener {
+            loadTargets(namespaceBox.selectedItem as? String, true)
+        }
+
+        val containerBox = ComboBox<String>().apply {
+            isEnabled = false
+        }
+        jbTargets.selectionModel.addListSelectionListener {
+            val target = jbTargets.selectedObject
+            containerBox.removeAllItems()
+            containerBox.addItem(defaultContainer)
+            target?.containers?.forEach { containerBox.addItem(it) }
+            target?.containers?.find { lastChosen == target.pathWithContainer(it) }?.let { containerBox.selectedItem = it }
+            containerBox.isEnabled = (target?.containers?.size ?: 0) > 1
+            updateOkAction()
+        }
 
-        val jbTargets = (targetsState.targets).asJBList()
         val searchField = JTextField().apply {
             val field = this
 
@@ -118,7 +319,7 @@ object MirrordExecDialog {
                     val searchTerm = field.text
                     if (!searchTerm.equals(searchPlaceHolder)) {
                         targetsState.searchPhrase = searchTerm
-                        jbTargets.setListData(targetsState.targets.toTypedArray())
+                        refreshTargets()
                     }
                 }
             })
@@ -144,56 +345,45 @@ object MirrordExecDialog {
                 }
             })
         }
-        val filterHelpers = listOf(
-            JBCheckBox("Pods", targetsState.pods).apply {
-                this.addActionListener {
-                    targetsState.pods = this.isSelected
-                    jbTargets.setListData(targetsState.targets.toTypedArray())
-                }
-            },
-            JBCheckBox("Deployments", targetsState.deployments).apply {
-                this.addActionListener {
-                    targetsState.deployments = this.isSelected
-                    jbTargets.setListData(targetsState.targets.toTypedArray())
-                }
-            },
-            JBCheckBox("Rollouts", targetsState.rollouts).apply {
-                this.addActionListener {
-                    targetsState.rollouts = this.isSelected
-                    jbTargets.setListData(targetsState.targets.toTypedArray())
-                }
-            }
-        )
-        val result = DialogBuilder().apply {
-            setCenterPanel(createSelectionDialog(jbTargets, searchField, filterHelpers))
-            setTitle(dialogHeading)
-            setPreferredFocusComponent(searchField)
-        }.show()
+
+        val rememberBox = configurationName?.let { JBCheckBox("Always use this target for \"$it\"") }
+
+        loadTargets(lastChosenNamespace, false)
+        val result = try {
+            dialog.apply {
+                setCenterPanel(createSelectionDialog(jbTargets, searchField, kindFilters, namespaceBox, refreshButton, statusLabel, containerBox, rememberBox))
+                setTitle(dialogHeading)
+                setPreferredFocusComponent(searchField)
+            }.show()
+        } finally {
+            loadingIndicator?.cancel()
+        }
 
         if (result == DialogWrapper.OK_EXIT_CODE) {
-            MirrordSettingsState.instance.mirrordState.showPodsInSelection = targetsState.pods
-            MirrordSettingsState.instance.mirrordState.showDeploymentsInSelection = targetsState.deployments
-            MirrordSettingsState.instance.mirrordState.showRolloutsInSelection = targetsState.rollouts
+            MirrordSettingsState.instance.mirrordState.targetKindsVisibility.putAll(targetsState.kindsVisibility)
 
-            if (jbTargets.isSelectionEmpty) {
-                // The user did not select any target, and clicked ok.
-                return targetlessTargetName
-            }
+            val namespace = requestedNamespace
+
+            val remember = rememberBox?.isSelected ?: false
 
-            val selectedValue = jbTargets.selectedValue
-            MirrordSettingsState.instance.mirrordState.lastChosenTarget = selectedValue
-            return selectedValue
+            // The user selected the targetless row, or did not select any row after the targets were listed, and clicked ok.
+            val selectedTarget = jbTargets.selectedObject ?: return Selection(targetlessTargetName, namespace, remember)
+
+            val selectedValue = if (selectedTarget === rememberedTarget && lastChosen != null) {
+                // Targets were not listed yet, use the last chosen target as it was.
+                lastChosen
+            } else {
+                val container = (containerBox.selectedItem as? String)?.takeIf { containerBox.isEnabled && it != defaultContainer }
+                selectedTarget.pathWithContainer(container)
+            }
+            return Selection(selectedValue, namespace, remember)
         }
 
         // The user clicked cancel, or closed the dialog.
         return null
     }
 
-    private fun List<String>.asJBList() = JBList(this).apply {
-        selectionMode = ListSelectionModel.SINGLE_SELECTION
-    }
-
-    private fun createSelectionDialog(items: JBList<String>, searchField: JTextField, filterHelpers: List<JComponent>): JPanel =
+    private fun createSelectionDialog(items: TableView<MirrordTarget?>, searchField: JTextField, kindFilters: JComponent, namespaceBox: JComboBox<String>, refreshButton: JButton, statusLabel: JLabel, containerBox: JComboBox<String>, rememberBox: JComponent?): JPanel =
         JPanel().apply {
             layout = BoxLayout(this, BoxLayout.Y_AXIS)
             border = JBUI.Borders.empty(10, 5)
@@ -205,14 +395,19 @@ object MirrordExecDialog {
             add(Box.createRigidArea(Dimension(0, 10)))
             add(
                 JBBox.createHorizontalBox().apply {
-                    filterHelpers.forEach {
-                        this.add(it)
-                        this.add(Box.createRigidArea(Dimension(10, 0)))
-                    }
+                    add(JLabel("Namespace:"))
+                    add(Box.createRigidArea(Dimension(10, 0)))
+                    add(namespaceBox)
+                    add(Box.createRigidArea(Dimension(5, 0)))
+                    add(refreshButton)
+                    add(Box.createRigidArea(Dimension(10, 0)))
+                    add(statusLabel)
                     alignmentX = JBBox.LEFT_ALIGNMENT
                 }
             )
             add(Box.createRigidArea(Dimension(0, 10)))
+            add(kindFilters)
+            add(Box.createRigidArea(Dimension(0, 10)))
             add(
                 searchField.apply {
                     alignmentX = JBScrollPane.LEFT_ALIGNMENT
@@ -222,13 +417,23 @@ object MirrordExecDialog {
             )
             add(Box.createRigidArea(Dimension(0, 10)))
             add(
-                JBScrollPane(
-                    items.apply {
-                        minimumSize = Dimension(250, 350)
-                    }
-                ).apply {
+                JBScrollPane(items).apply {
                     alignmentX = JBScrollPane.LEFT_ALIGNMENT
+                    preferredSize = Dimension(650, 350)
                 }
             )
+            add(Box.createRigidArea(Dimension(0, 10)))
+            add(
+                JBBox.createHorizontalBox().apply {
+                    add(JLabel("Container:"))
+                    add(Box.createRigidArea(Dimension(10, 0)))
+                    add(containerBox)
+                    alignmentX = JBBox.LEFT_ALIGNMENT
+                }
+            )
+            rememberBox?.let {
+                add(Box.createRigidArea(Dimension(0, 10)))
+                add(it.apply { alignmentX = JComponent.LEFT_ALIGNMENT })
+            }
         }
 }
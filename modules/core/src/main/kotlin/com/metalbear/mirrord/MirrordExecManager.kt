@@ -1,5 +1,7 @@
 package com.metalbear.mirrord
 
+import com.intellij.execution.CommonProgramRunConfigurationParameters
+import com.intellij.execution.configurations.RunConfigurationBase
 import com.intellij.execution.wsl.WSLDistribution
 import com.intellij.notification.NotificationType
 import com.intellij.openapi.application.ApplicationManager
@@ -7,6 +9,7 @@ import com.intellij.openapi.application.WriteAction
 import com.intellij.openapi.components.service
 import com.intellij.openapi.fileEditor.FileEditorManager
 import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.progress.ProgressIndicator
 import com.intellij.openapi.util.SystemInfo
 
 /**
@@ -17,37 +20,83 @@ import com.intellij.openapi.util.SystemInfo
 class MirrordExecManager(private val service: MirrordProjectService) {
     /**
      * Attempts to show the target selection dialog and allow user to select the mirrord target.
+     * If the user chose to always use a target for the run configuration, returns that target without showing the dialog.
      *
-     * @return target chosen by the user (or special constant for targetless mode)
+     * @param configuration run configuration being started, null if not known
+     * @return target chosen by the user (or special constant for targetless mode) and the namespace override
      * @throws ProcessCanceledException if the dialog cannot be displayed
      */
     private fun chooseTarget(
         cli: String,
         wslDistribution: WSLDistribution?,
         config: String?,
-        mirrordApi: MirrordApi
-    ): String {
+        mirrordApi: MirrordApi,
+        projectEnvVars: Map<String, String>?,
+        configuration: RunConfigurationBase<*>?
+    ): MirrordExecDialog.Selection {
         MirrordLogger.logger.debug("choose target called")
 
-        val pods = mirrordApi.listPods(
-            cli,
-            config,
-            wslDistribution
-        )
+        val options = configuration?.let { MirrordRunConfigurationOptions.get(it) } ?: MirrordRunConfigurationOptions()
+        val rememberedTarget = options.targetToUse
+        if (configuration != null && rememberedTarget != null) {
+            MirrordLogger.logger.debug("using target remembered for the run configuration")
+            val targetName = rememberedTarget.takeUnless { it == MirrordExecDialog.targetlessTargetName } ?: "targetless mode"
+            service
+                .notifier
+                .notification(
+                    "mirrord is using $targetName, remembered for \"${configuration.name}\".",
+                    NotificationType.INFORMATION
+                )
+                .withAction("Ask Every Time") { _, notification ->
+                    MirrordRunConfigurationOptions.set(
+                        configuration,
+                        options.copy(targetMode = MirrordRunConfigurationOptions.TargetMode.ASK)
+                    )
+                    notification.expire()
+                }
+                .fire()
+
+            return MirrordExecDialog.Selection(rememberedTarget, options.rememberedNamespace)
+        }
+
+        val lastChosen = options.rememberedTarget
+
+        // Called by the dialog on a background thread, initially, on refresh and when the user picks a different namespace.
+        val listTargets = { namespace: String?, refresh: Boolean, indicator: ProgressIndicator ->
+            try {
+                // Captured, so that the background refresh of the cache does not pick up a different context.
+                val kubeContext = service.kubeContext
+                // The environment is part of the key, so that the listing is refreshed with the environment it was listed with.
+                val key = service.targetCache.key(namespace, config, projectEnvVars)
+                val onLoaded = { listing: MirrordTargetListing ->
+                    if (listing.targets.isEmpty()) {
+                        service.notifier.notifySimple("No mirrord target available in the configured namespace. You can run targetless, or set a different target namespace or kubeconfig in the mirrord configuration file.", NotificationType.INFORMATION)
+                    }
+                }
+                service.targetCache.get(key, refresh, indicator, onLoaded) {
+                    mirrordApi.listPods(cli, config, wslDistribution, namespace, kubeContext, it)
+                }
+            } catch (e: MirrordError) {
+                e.showHelp(service.project)
+                null
+            } catch (e: ProcessCanceledException) {
+                null
+            }
+        }
 
         val application = ApplicationManager.getApplication()
 
         val selected = if (application.isDispatchThread) {
             MirrordLogger.logger.debug("dispatch thread detected, choosing target on current thread")
-            MirrordExecDialog.selectTargetDialog(pods)
+            MirrordExecDialog.selectTargetDialog(lastChosen, options.rememberedNamespace, configuration?.name, listTargets)
         } else if (!application.isReadAccessAllowed) {
             MirrordLogger.logger.debug("no read lock detected, choosing target on dispatch thread")
-            var target: String? = null
+            var selection: MirrordExecDialog.Selection? = null
             application.invokeAndWait {
                 MirrordLogger.logger.debug("choosing target from invoke")
-                target = MirrordExecDialog.selectTargetDialog(pods)
+                selection = MirrordExecDialog.selectTargetDialog(lastChosen, options.rememberedNamespace, configuration?.name, listTargets)
             }
-            target
+            selection
         } else {
             MirrordLogger.logger.debug("read lock detected, aborting target selection")
 
@@ -76,7 +125,69 @@ class MirrordExecManager(private val service: MirrordProjectService) {
             null
         }
 
-        return selected ?: throw ProcessCanceledException()
+        val selection = selected ?: throw ProcessCanceledException()
+
+        configuration?.let {
+            val targetMode = if (selection.remember) {
+                MirrordRunConfigurationOptions.TargetMode.REMEMBERED
+            } else {
+                MirrordRunConfigurationOptions.TargetMode.ASK
+            }
+            MirrordRunConfigurationOptions.set(
+                it,
+                options.copy(rememberedTarget = selection.target, rememberedNamespace = selection.namespace, targetMode = targetMode)
+            )
+        }
+
+        return selection
+    }
+
+    /**
+     * Finds the config file for a run: the config set in the mirrord tab of the run configuration (or bound from the dropdown),
+     * the active config, the config set in the `MIRRORD_CONFIG_FILE` environment variable, or the default config.
+     * Path macros in the config set in the run configuration are expanded, see [MirrordPathMacros].
+     *
+     * @param projectEnvVars environment of the run configuration
+     * @param configuration run configuration being started, null if not known
+     * @return null if there is no config file
+     * @throws MirrordError if the macros in the config path cannot be expanded
+     */
+    fun configPath(
+        options: MirrordRunConfigurationOptions,
+        projectEnvVars: Map<String, String>?,
+        configuration: RunConfigurationBase<*>?
+    ): String? {
+        val configFromOptions = options.configFile?.let { MirrordPathMacros.expandConfigPath(service.project, configuration, it) }
+        // `MIRRORD_CONFIG_FILE` is kept for run configurations created before the mirrord tab existed.
+        val configFromEnv = projectEnvVars
+            ?.get(CONFIG_ENV_NAME)
+            // The active config takes precedence, so there is no need to expand (and report) macros in an unused path.
+            ?.takeIf { configFromOptions == null && service.activeConfig == null }
+            ?.let { MirrordPathMacros.expandConfigPath(service.project, configuration, it) }
+        return service.configApi.getConfigPath(configFromEnv, configFromOptions, configuration)
+    }
+
+    /**
+     * Runs `mirrord verify-config` on the given config file, without displaying the errors and warnings.
+     *
+     * @param product for example "idea", "goland"
+     * @param projectEnvVars environment of the run configuration
+     */
+    fun verifyConfig(
+        configPath: String,
+        wslDistribution: WSLDistribution?,
+        product: String,
+        projectEnvVars: Map<String, String>?
+    ): MirrordVerifiedConfig {
+        if (SystemInfo.isWindows && wslDistribution == null) {
+            throw MirrordError("can't use on Windows without WSL")
+        }
+
+        val cli = cliPath(wslDistribution, product)
+        val output = service
+            .mirrordApi(projectEnvVars)
+            .verifyConfig(cli, wslDistribution?.getWslPath(configPath) ?: configPath, wslDistribution)
+        return MirrordVerifiedConfig(output, null)
     }
 
     private fun cliPath(wslDistribution: WSLDistribution?, product: String): String {
@@ -88,6 +199,7 @@ class MirrordExecManager(private val service: MirrordProjectService) {
      * Starts mirrord, shows dialog for selecting pod if target is not set and returns env to set.
      *
      * @param envVars Contains both system env vars, and (active) launch settings, see `Wrapper`.
+     * @param configuration run configuration being started, used to remember the selected target
      * @return extra environment variables to set for the executed process and path to the patched executable.
      * null if mirrord service is disabled
      * @throws ProcessCanceledException if the user cancelled
@@ -96,15 +208,93 @@ class MirrordExecManager(private val service: MirrordProjectService) {
         wslDistribution: WSLDistribution?,
         executable: String?,
         product: String,
-        projectEnvVars: Map<String, String>?
+        projectEnvVars: Map<String, String>?,
+        configuration: RunConfigurationBase<*>?
     ): MirrordExecution? {
         MirrordLogger.logger.debug("MirrordExecManager.start")
-        val explicitlyEnabled = projectEnvVars?.any { (key, value) -> key == "MIRRORD_ACTIVE" && value == "1" } ?: false
-        if (!service.enabled && !explicitlyEnabled) {
+        val options = configuration?.let { MirrordRunConfigurationOptions.get(it) } ?: MirrordRunConfigurationOptions()
+        val enabled = when (options.enableMode) {
+            MirrordRunConfigurationOptions.EnableMode.ENABLED -> true
+            MirrordRunConfigurationOptions.EnableMode.DISABLED -> false
+            // `MIRRORD_ACTIVE` is kept for run configurations created before the mirrord tab existed.
+            MirrordRunConfigurationOptions.EnableMode.DEFAULT -> service.enabled || projectEnvVars?.get(ACTIVE_ENV_NAME) == "1"
+        }
+        if (!enabled) {
             MirrordLogger.logger.debug("disabled, returning")
             return null
         }
 
+        val prepared = prepare(wslDistribution, product, projectEnvVars, configuration, options)
+
+        val run = configuration?.let { service.project.service<MirrordStartingRuns>().find(it) }
+        val session = service.sessions.startSession(prepared.target, run)
+        val executionInfo = prepared.mirrordApi.exec(
+            prepared.cli,
+            prepared.target,
+            prepared.namespace,
+            prepared.configPath,
+            executable,
+            wslDistribution,
+            session
+        )
+        MirrordLogger.logger.debug("MirrordExecManager.start: executionInfo: $executionInfo")
+
+        val result = if (options.envPrecedence == MirrordRunConfigurationOptions.EnvPrecedence.LOCAL && projectEnvVars != null) {
+            executionInfo.copy(
+                environment = executionInfo.environment.filterKeys { !projectEnvVars.containsKey(it) }.toMutableMap(),
+                envToUnset = executionInfo.envToUnset?.filterNot { projectEnvVars.containsKey(it) }
+            )
+        } else {
+            executionInfo
+        }
+
+        result.environment["MIRRORD_IGNORE_DEBUGGER_PORTS"] = "35000-65535"
+        session.recordExecution(result, processEnv(wslDistribution, projectEnvVars, configuration))
+        return result
+    }
+
+    /**
+     * The products pass only the environment of the run configuration, while the process also inherits
+     * the environment of the IDE, unless the run configuration disables it or the process runs in WSL.
+     *
+     * @return environment of the process without mirrord
+     */
+    private fun processEnv(
+        wslDistribution: WSLDistribution?,
+        projectEnvVars: Map<String, String>?,
+        configuration: RunConfigurationBase<*>?
+    ): Map<String, String> {
+        val passParentEnvs = (configuration as? CommonProgramRunConfigurationParameters)?.isPassParentEnvs ?: true
+        val parentEnv = if (wslDistribution == null && passParentEnvs) System.getenv() else emptyMap()
+        return parentEnv + projectEnvVars.orEmpty()
+    }
+
+    /**
+     * Everything needed to invoke the mirrord binary for a run.
+     *
+     * @param target target to pass to the mirrord binary, null if the target is taken from the config or mirrord runs targetless
+     * @param namespace target namespace override
+     */
+    private class Prepared(
+        val mirrordApi: MirrordApi,
+        val cli: String,
+        val configPath: String?,
+        val target: String?,
+        val namespace: String?
+    )
+
+    /**
+     * Resolves the mirrord binary and the config, verifies the config and selects the target.
+     *
+     * @throws ProcessCanceledException if the user cancelled
+     */
+    private fun prepare(
+        wslDistribution: WSLDistribution?,
+        product: String,
+        projectEnvVars: Map<String, String>?,
+        configuration: RunConfigurationBase<*>?,
+        options: MirrordRunConfigurationOptions
+    ): Prepared {
         if (SystemInfo.isWindows && wslDistribution == null) {
             throw MirrordError("can't use on Windows without WSL")
         }
@@ -122,39 +312,23 @@ class MirrordExecManager(private val service: MirrordProjectService) {
 
         val mirrordApi = service.mirrordApi(projectEnvVars)
 
-        val mirrordConfigPath = projectEnvVars?.get(CONFIG_ENV_NAME)?.let {
-            if (it.contains("\$ProjectPath\$")) {
-                val projectFile = service.configApi.getProjectDir()
-                projectFile.canonicalPath?.let { path ->
-                    it.replace("\$ProjectPath\$", path)
-                } ?: run {
-                    service.notifier.notifySimple(
-                        "Failed to evaluate `ProjectPath` macro used in `$CONFIG_ENV_NAME` environment variable",
-                        NotificationType.WARNING
-                    )
-                    it
-                }
-            } else {
-                it
-            }
-        }
         val cli = cliPath(wslDistribution, product)
 
-        MirrordLogger.logger.debug("MirrordExecManager.start: mirrord cli path is $cli")
+        MirrordLogger.logger.debug("MirrordExecManager.prepare: mirrord cli path is $cli")
         // Find the mirrord config path, then call `mirrord verify-config {path}` so we can display warnings/errors
         // from the config without relying on mirrord-layer.
 
-        val configPath = service.configApi.getConfigPath(mirrordConfigPath)
-        MirrordLogger.logger.debug("MirrordExecManager.start: config path is $configPath")
+        val configPath = configPath(options, projectEnvVars, configuration)
+        MirrordLogger.logger.debug("MirrordExecManager.prepare: config path is $configPath")
 
         val verifiedConfig = configPath?.let {
             val verifiedConfigOutput =
                 mirrordApi.verifyConfig(cli, wslDistribution?.getWslPath(it) ?: it, wslDistribution)
-            MirrordLogger.logger.debug("MirrordExecManager.start: verifiedConfigOutput: $verifiedConfigOutput")
+            MirrordLogger.logger.debug("MirrordExecManager.prepare: verifiedConfigOutput: $verifiedConfigOutput")
             MirrordVerifiedConfig(verifiedConfigOutput, service.notifier).apply {
-                MirrordLogger.logger.debug("MirrordExecManager.start: MirrordVerifiedConfig: $it")
+                MirrordLogger.logger.debug("MirrordExecManager.prepare: MirrordVerifiedConfig: $it")
                 if (isError()) {
-                    MirrordLogger.logger.debug("MirrordExecManager.start: invalid config error")
+                    MirrordLogger.logger.debug("MirrordExecManager.prepare: invalid config error")
                     throw InvalidConfigException(it, "Validation failed for config")
                 }
             }
@@ -163,12 +337,20 @@ class MirrordExecManager(private val service: MirrordProjectService) {
         MirrordLogger.logger.debug("Verified Config: $verifiedConfig, Target selection.")
 
         val targetSet = verifiedConfig?.let { isTargetSet(it.config) } ?: false
-        val target = if (!targetSet) {
+        val selection = if (options.targetOverride != null) {
+            MirrordLogger.logger.debug("target overridden in the run configuration")
+            MirrordExecDialog.Selection(options.targetOverride, null)
+        } else if (!targetSet) {
             // There is no config file or the config does not specify a target, so show dialog.
             MirrordLogger.logger.debug("target not selected, showing dialog")
 
-            chooseTarget(cli, wslDistribution, configPath, mirrordApi)
-                .takeUnless { it == MirrordExecDialog.targetlessTargetName } ?: run {
+            chooseTarget(cli, wslDistribution, configPath, mirrordApi, projectEnvVars, configuration)
+        } else {
+            null
+        }
+
+        val target = selection?.let { selected ->
+            selected.target.takeUnless { it == MirrordExecDialog.targetlessTargetName } ?: run {
                 MirrordLogger.logger.info("No target specified - running targetless")
                 service.notifier.notification(
                     "No target specified, mirrord running targetless.",
@@ -179,21 +361,9 @@ class MirrordExecManager(private val service: MirrordProjectService) {
 
                 null
             }
-        } else {
-            null
         }
 
-        val executionInfo = mirrordApi.exec(
-            cli,
-            target,
-            configPath,
-            executable,
-            wslDistribution
-        )
-        MirrordLogger.logger.debug("MirrordExecManager.start: executionInfo: $executionInfo")
-
-        executionInfo.environment["MIRRORD_IGNORE_DEBUGGER_PORTS"] = "35000-65535"
-        return executionInfo
+        return Prepared(mirrordApi, cli, configPath, target, selection?.namespace)
     }
 
     /**
@@ -205,9 +375,14 @@ class MirrordExecManager(private val service: MirrordProjectService) {
         var wsl: WSLDistribution? = null
         var executable: String? = null
 
+        /**
+         * Run configuration being started, if known. Used to remember the selected target per run configuration.
+         */
+        var configuration: RunConfigurationBase<*>? = null
+
         fun start(): MirrordExecution? {
             return try {
-                manager.start(wsl, executable, product, extraEnvVars)
+                manager.start(wsl, executable, product, extraEnvVars, configuration)
             } catch (e: MirrordError) {
                 e.showHelp(manager.service.project)
                 throw e
@@ -0,0 +1,212 @@
+@file:Suppress("UnstableApiUsage")
+
+package com.metalbear.mirrord
+
+import com.intellij.execution.CommonProgramRunConfigurationParameters
+import com.intellij.execution.ExecutionException
+import com.intellij.execution.Executor
+import com.intellij.execution.configurations.CommandLineState
+import com.intellij.execution.configurations.ConfigurationFactory
+import com.intellij.execution.configurations.ConfigurationTypeBase
+import com.intellij.execution.configurations.GeneralCommandLine
+import com.intellij.execution.configurations.LocatableConfigurationBase
+import com.intellij.execution.configurations.LocatableRunConfigurationOptions
+import com.intellij.execution.configurations.RunConfiguration
+import com.intellij.execution.configurations.RunProfileState
+import com.intellij.execution.configurations.RuntimeConfigurationError
+import com.intellij.execution.process.KillableColoredProcessHandler
+import com.intellij.execution.process.ProcessHandler
+import com.intellij.execution.process.ProcessTerminatedListener
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.execution.target.LanguageRuntimeType
+import com.intellij.execution.target.TargetEnvironmentAwareRunProfile
+import com.intellij.execution.target.TargetEnvironmentConfiguration
+import com.intellij.execution.target.createEnvironmentRequest
+import com.intellij.execution.wsl.WSLCommandLineOptions
+import com.intellij.execution.wsl.target.WslTargetEnvironmentConfiguration
+import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
+import com.intellij.openapi.application.ApplicationNamesInfo
+import com.intellij.openapi.components.BaseState
+import com.intellij.openapi.components.service
+import com.intellij.openapi.options.SettingsEditor
+import com.intellij.openapi.options.SettingsEditorGroup
+import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.project.Project
+import com.intellij.util.execution.ParametersListUtil
+import icons.MirrordIcons
+import org.jdom.Element
+
+/**
+ * Run configuration type that runs any executable with mirrord.
+ */
+class MirrordExecConfigurationType : ConfigurationTypeBase(
+    ID,
+    "mirrord exec",
+    "Runs an executable with mirrord",
+    MirrordIcons.enabled
+) {
+    companion object {
+        const val ID = "MirrordExecConfiguration"
+    }
+
+    init {
+        addFactory(MirrordExecConfigurationFactory(this))
+    }
+}
+
+class MirrordExecConfigurationFactory(type: MirrordExecConfigurationType) : ConfigurationFactory(type) {
+    override fun getId(): String = MirrordExecConfigurationType.ID
+
+    /**
+     * Unlike in the other run configurations, mirrord is enabled regardless of the toolbar toggle by default.
+     */
+    override fun createTemplateConfiguration(project: Project): RunConfiguration {
+        return MirrordExecRunConfiguration(project, this, "mirrord exec").also {
+            MirrordRunConfigurationOptions.set(it, MirrordRunConfigurationOptions(enableMode = MirrordRunConfigurationOptions.EnableMode.ENABLED))
+        }
+    }
+
+    override fun getOptionsClass(): Class<out BaseState> = MirrordExecRunConfigurationOptions::class.java
+}
+
+class MirrordExecRunConfigurationOptions : LocatableRunConfigurationOptions() {
+    var executable by string()
+    var arguments by string()
+    var workingDirectory by string()
+    var env by map<String, String>()
+    var passParentEnvs by property(true)
+
+    /**
+     * Name of the run target, null to run locally.
+     */
+    var runTarget by string()
+}
+
+/**
+ * Runs [executable] with mirrord. The mirrord options are stored in [MirrordRunConfigurationOptions] and mirrord is started
+ * with [MirrordExecManager.wrapper], like in the other run configurations, then the executable is started with the environment from mirrord.
+ * On Windows, it runs on a WSL run target, like the other run configurations.
+ */
+class MirrordExecRunConfiguration(project: Project, factory: ConfigurationFactory, name: String) :
+    LocatableConfigurationBase<MirrordExecRunConfigurationOptions>(project, factory, name),
+    CommonProgramRunConfigurationParameters,
+    TargetEnvironmentAwareRunProfile {
+
+    public override fun getOptions(): MirrordExecRunConfigurationOptions {
+        return super.getOptions() as MirrordExecRunConfigurationOptions
+    }
+
+    var executable: String?
+        get() = options.executable
+        set(value) {
+            options.executable = value
+        }
+
+    override fun getProgramParameters(): String? = options.arguments
+
+    override fun setProgramParameters(value: String?) {
+        options.arguments = value
+    }
+
+    override fun getWorkingDirectory(): String? = options.workingDirectory
+
+    override fun setWorkingDirectory(value: String?) {
+        options.workingDirectory = value
+    }
+
+    override fun getEnvs(): Map<String, String> = options.env
+
+    override fun setEnvs(envs: Map<String, String>) {
+        options.env = envs.toMutableMap()
+    }
+
+    override fun isPassParentEnvs(): Boolean = options.passParentEnvs
+
+    override fun setPassParentEnvs(passParentEnvs: Boolean) {
+        options.passParentEnvs = passParentEnvs
+    }
+
+    override fun canRunOn(target: TargetEnvironmentConfiguration): Boolean = target is WslTargetEnvironmentConfiguration
+
+    override fun getDefaultLanguageRuntimeType(): LanguageRuntimeType<*>? = null
+
+    override fun getDefaultTargetName(): String? = options.runTarget
+
+    override fun setDefaultTargetName(targetName: String?) {
+        options.runTarget = targetName
+    }
+
+    override fun getConfigurationEditor(): SettingsEditor<out RunConfiguration> {
+        return SettingsEditorGroup<MirrordExecRunConfiguration>().apply {
+            addEditor("Configuration", MirrordExecRunConfigurationEditor(project))
+            addEditor(MirrordRunConfigurationEditor.TITLE, MirrordRunConfigurationEditor(project))
+        }
+    }
+
+    override fun checkConfiguration() {
+        if (executable.isNullOrBlank()) {
+            throw RuntimeConfigurationError("Executable is not specified")
+        }
+    }
+
+    override fun readExternal(element: Element) {
+        super.readExternal(element)
+        MirrordRunConfigurationOptions.readExternal(this, element)
+    }
+
+    override fun writeExternal(element: Element) {
+        super.writeExternal(element)
+        MirrordRunConfigurationOptions.writeExternal(this, element)
+    }
+
+    override fun getState(executor: Executor, environment: ExecutionEnvironment): RunProfileState {
+        return object : CommandLineState(environment) {
+            override fun startProcess(): ProcessHandler {
+                val binary = executable?.takeIf { it.isNotBlank() } ?: throw ExecutionException("Executable is not specified")
+                val service = project.service<MirrordProjectService>()
+
+                val wsl = when (val request = createEnvironmentRequest(this@MirrordExecRunConfiguration, project)) {
+                    is WslTargetEnvironmentRequest -> request.configuration.distribution
+                    else -> null
+                }
+                val path = wsl?.getWslPath(binary) ?: binary
+
+                val executionInfo = try {
+                    service.execManager.wrapper(ApplicationNamesInfo.getInstance().productName.lowercase(), envs).apply {
+                        this.wsl = wsl
+                        this.executable = path
+                        this.configuration = this@MirrordExecRunConfiguration
+                    }.start()
+                } catch (e: MirrordError) {
+                    throw ExecutionException(e.message, e)
+                } catch (e: ProcessCanceledException) {
+                    throw ExecutionException("mirrord was cancelled", e)
+                }
+
+                val commandLine = GeneralCommandLine(listOf(executionInfo?.patchedPath ?: path) + ParametersListUtil.parse(programParameters.orEmpty()))
+                    .withWorkDirectory(workingDirectory?.takeIf { it.isNotBlank() } ?: project.basePath)
+                    .withParentEnvironmentType(if (isPassParentEnvs) GeneralCommandLine.ParentEnvironmentType.CONSOLE else GeneralCommandLine.ParentEnvironmentType.NONE)
+                    .withEnvironment(envs)
+                executionInfo?.let {
+                    commandLine.withEnvironment(it.environment)
+                    for (key in it.envToUnset.orEmpty()) {
+                        commandLine.environment.remove(key)
+                    }
+                }
+
+                wsl?.patchCommandLine(
+                    commandLine,
+                    project,
+                    WSLCommandLineOptions().apply {
+                        isLaunchWithWslExe = true
+                        isExecuteCommandInShell = false
+                    }
+                )
+
+                val handler = KillableColoredProcessHandler(commandLine)
+                ProcessTerminatedListener.attach(handler)
+                return handler
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.configuration.EnvironmentVariablesTextFieldWithBrowseButton
+import com.intellij.openapi.fileChooser.FileChooserDescriptorFactory
+import com.intellij.openapi.options.SettingsEditor
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.TextFieldWithBrowseButton
+import com.intellij.ui.RawCommandLineEditor
+import com.intellij.ui.components.JBTextField
+import com.intellij.util.ui.FormBuilder
+import javax.swing.JComponent
+import javax.swing.JPanel
+
+/**
+ * Editor of the executable of [MirrordExecRunConfiguration].
+ * The mirrord options are edited in the same [MirrordRunConfigurationEditor] tab as in the other run configurations.
+ */
+class MirrordExecRunConfigurationEditor(project: Project) : SettingsEditor<MirrordExecRunConfiguration>() {
+    private val executable = TextFieldWithBrowseButton().apply {
+        addBrowseFolderListener("Executable", null, project, FileChooserDescriptorFactory.createSingleFileDescriptor())
+    }
+
+    private val arguments = RawCommandLineEditor()
+
+    private val workingDirectory = TextFieldWithBrowseButton().apply {
+        addBrowseFolderListener("Working Directory", null, project, FileChooserDescriptorFactory.createSingleFolderDescriptor())
+        (textField as? JBTextField)?.emptyText?.text = "Project directory"
+    }
+
+    private val env = EnvironmentVariablesTextFieldWithBrowseButton()
+
+    override fun resetEditorFrom(s: MirrordExecRunConfiguration) {
+        executable.text = s.executable.orEmpty()
+        arguments.text = s.programParameters.orEmpty()
+        workingDirectory.text = s.workingDirectory.orEmpty()
+        env.envs = s.envs
+        env.isPassParentEnvs = s.isPassParentEnvs
+    }
+
+    override fun applyEditorTo(s: MirrordExecRunConfiguration) {
+        s.executable = executable.text.trim()
+        s.programParameters = arguments.text
+        s.workingDirectory = workingDirectory.text.trim().ifEmpty { null }
+        s.envs = env.envs
+        s.isPassParentEnvs = env.isPassParentEnvs
+    }
+
+    override fun createEditor(): JComponent = FormBuilder
+        .createFormBuilder()
+        .addLabeledComponent("Executable:", executable)
+        .addLabeledComponent("Arguments:", arguments)
+        .addLabeledComponent("Working directory:", workingDirectory)
+        .addLabeledComponent("Environment variables:", env)
+        .addComponentFillVertically(JPanel(), 0)
+        .panel
+}
@@ -0,0 +1,181 @@
+package com.metalbear.mirrord
+
+import com.google.gson.JsonObject
+import com.intellij.execution.runners.ExecutionUtil
+import com.intellij.ide.BrowserUtil
+import com.intellij.openapi.fileEditor.OpenFileDescriptor
+import com.intellij.openapi.ide.CopyPasteManager
+import com.intellij.openapi.options.ShowSettingsUtil
+import com.intellij.openapi.vfs.LocalFileSystem
+import java.awt.datatransfer.StringSelection
+import java.util.concurrent.ConcurrentHashMap
+
+/**
+ * Rust enum equivalent to the `IdeAction`.
+ *
+ * Converted from a `JsonObject` from `IdeMessage` with the [IdeActionRegistry].
+ */
+sealed class IdeAction {
+    /**
+     * The text of the action, visible in the notification.
+     */
+    abstract val label: String
+
+    /**
+     * A link action that appears in the notification, such as "Get help".
+     *
+     * @param label The text of the link: "Get help".
+     * @param link The Url.
+     */
+    data class Link(override val label: String, val link: String) : IdeAction()
+
+    /**
+     * Opens a file in the editor, for example the config file that caused a warning.
+     *
+     * @param line 1-based line to navigate to, null to open the file at the beginning.
+     */
+    data class OpenFile(override val label: String, val path: String, val line: Int?) : IdeAction()
+
+    /**
+     * Opens the mirrord plugin settings.
+     */
+    data class OpenSettings(override val label: String) : IdeAction()
+
+    /**
+     * Copies the given text to the clipboard, for example a command to run in the terminal.
+     */
+    data class CopyToClipboard(override val label: String, val text: String) : IdeAction()
+
+    /**
+     * Restarts the run that sent the action with extra environment variables passed to the mirrord binary,
+     * for example to change a config option with a `MIRRORD_*` override.
+     */
+    data class RerunWithEnv(override val label: String, val env: Map<String, String>) : IdeAction()
+
+    /**
+     * An action this version of the plugin does not know how to handle. Displayed as plain text.
+     */
+    data class Unknown(val kind: String, override val label: String) : IdeAction()
+}
+
+/**
+ * Parses the `IdeAction`s received from the mirrord binary and performs them.
+ *
+ * Every `kind` of the action is registered with a parser and a handler, unknown kinds are parsed to [IdeAction.Unknown].
+ */
+object IdeActionRegistry {
+    private class Entry<T : IdeAction>(
+        val type: Class<T>,
+        val parse: (JsonObject) -> T?,
+        val perform: (T, MirrordProjectService, MirrordSession) -> Unit
+    ) {
+        fun performUnchecked(action: IdeAction, service: MirrordProjectService, session: MirrordSession) {
+            perform(type.cast(action), service, session)
+        }
+    }
+
+    private val entries: MutableMap<String, Entry<*>> = ConcurrentHashMap()
+
+    /**
+     * Registers a new `kind` of the `IdeAction`.
+     *
+     * @param parse returns null if the action is invalid
+     * @param perform called on the event dispatch thread when the user clicks the action,
+     * with the session of the mirrord invocation that sent the action
+     */
+    fun <T : IdeAction> register(
+        kind: String,
+        type: Class<T>,
+        parse: (JsonObject) -> T?,
+        perform: (T, MirrordProjectService, MirrordSession) -> Unit
+    ) {
+        entries[kind] = Entry(type, parse, perform)
+    }
+
+    /**
+     * @return the parsed action, or [IdeAction.Unknown] if the kind is not registered or the action is invalid.
+     */
+    fun parse(json: JsonObject): IdeAction {
+        val kind = json.string("kind") ?: "unknown"
+        val label = json.string("label") ?: kind
+
+        val action = try {
+            entries[kind]?.parse?.invoke(json)
+        } catch (e: Exception) {
+            MirrordLogger.logger.debug("failed to parse IdeAction $json", e)
+            null
+        }
+
+        return action ?: IdeAction.Unknown(kind, label).also {
+            MirrordLogger.logger.info("received an unsupported IdeAction of kind `$kind`: $json")
+        }
+    }
+
+    /**
+     * @throws MirrordError if the action failed
+     */
+    fun perform(action: IdeAction, service: MirrordProjectService, session: MirrordSession) {
+        entries
+            .values
+            .find { it.type.isInstance(action) }
+            ?.performUnchecked(action, service, session)
+            ?: MirrordLogger.logger.info("no handler for IdeAction $action")
+    }
+
+    private fun JsonObject.string(name: String): String? {
+        return get(name)?.takeIf { it.isJsonPrimitive }?.asString
+    }
+
+    init {
+        register("Link", IdeAction.Link::class.java, { json ->
+            val label = json.string("label") ?: return@register null
+            val link = json.string("link") ?: return@register null
+            IdeAction.Link(label, link)
+        }) { action, _, _ ->
+            val link = action.link
+                .replace("utm_medium=plugin", "utm_medium=intellij")
+                .replace("utm_medium=cli", "utm_medium=intellij")
+            BrowserUtil.browse(link)
+        }
+
+        register("OpenFile", IdeAction.OpenFile::class.java, { json ->
+            val label = json.string("label") ?: return@register null
+            val path = json.string("path") ?: return@register null
+            IdeAction.OpenFile(label, path, json.string("line")?.toIntOrNull())
+        }) { action, service, _ ->
+            val file = LocalFileSystem.getInstance().refreshAndFindFileByPath(action.path)
+                ?: throw MirrordError("file ${action.path} not found")
+            val line = action.line?.let { it - 1 }?.coerceAtLeast(0) ?: 0
+            OpenFileDescriptor(service.project, file, line, 0).navigate(true)
+        }
+
+        register("OpenSettings", IdeAction.OpenSettings::class.java, { json ->
+            json.string("label")?.let { IdeAction.OpenSettings(it) }
+        }) { _, service, _ ->
+            ShowSettingsUtil.getInstance().showSettingsDialog(service.project, MirrordSettingsConfigurable::class.java)
+        }
+
+        register("CopyToClipboard", IdeAction.CopyToClipboard::class.java, { json ->
+            val label = json.string("label") ?: return@register null
+            val text = json.string("text") ?: return@register null
+            IdeAction.CopyToClipboard(label, text)
+        }) { action, _, _ ->
+            CopyPasteManager.getInstance().setContents(StringSelection(action.text))
+        }
+
+        register("RerunWithEnv", IdeAction.RerunWithEnv::class.java, { json ->
+            val label = json.string("label") ?: return@register null
+            val env = json.getAsJsonObject("env")
+                ?.entrySet()
+                ?.filter { it.value.isJsonPrimitive }
+                ?.associate { it.key to it.value.asString }
+                ?: return@register null
+            IdeAction.RerunWithEnv(label, env)
+        }) { action, service, session ->
+            // Restarts the run that sent the action, not the selected one, which may be unrelated.
+            val run = session.run ?: throw MirrordError("can't rerun, the run that started mirrord is not known")
+            service.overrideNextRunEnv(run.runProfile, action.env)
+            ExecutionUtil.restart(run)
+        }
+    }
+}
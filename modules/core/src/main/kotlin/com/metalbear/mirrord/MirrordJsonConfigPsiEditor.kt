@@ -0,0 +1,78 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.JsonNode
+import com.fasterxml.jackson.databind.node.JsonNodeFactory
+import com.intellij.json.JsonElementTypes
+import com.intellij.json.psi.JsonElementGenerator
+import com.intellij.json.psi.JsonFile
+import com.intellij.json.psi.JsonObject
+import com.intellij.json.psi.JsonProperty
+import com.intellij.json.psi.JsonPsiUtil
+import com.intellij.json.psi.JsonValue
+import com.intellij.openapi.editor.Document
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import com.intellij.psi.codeStyle.CodeStyleManager
+import com.intellij.psi.util.PsiTreeUtil
+
+/**
+ * Edits json configs through the JSON PSI.
+ */
+class MirrordJsonConfigPsiEditor : MirrordConfigPsiEditor {
+    override fun accepts(file: PsiFile): Boolean = file is JsonFile
+
+    override fun apply(file: PsiFile, document: Document, edits: List<MirrordConfigEdit>): Boolean {
+        val root = (file as JsonFile).topLevelValue as? JsonObject ?: return false
+        val generator = JsonElementGenerator(file.project)
+        edits.forEach { apply(root, it.path, it.value, generator) }
+        return true
+    }
+
+    override fun findKey(file: PsiFile, path: List<String>): PsiElement? {
+        var jsonObject = (file as JsonFile).topLevelValue as? JsonObject ?: return null
+        path.dropLast(1).forEach { name ->
+            jsonObject = jsonObject.findProperty(name)?.value as? JsonObject ?: return null
+        }
+        return jsonObject.findProperty(path.last())?.nameElement
+    }
+
+    private fun apply(jsonObject: JsonObject, path: List<String>, value: JsonNode?, generator: JsonElementGenerator) {
+        val name = path.first()
+        val property = jsonObject.findProperty(name)
+
+        if (path.size > 1) {
+            (property?.value as? JsonObject)?.let { return apply(it, path.drop(1), value, generator) }
+            value ?: return
+            // The parent objects are missing, they are created together with the value.
+            val nested = path.drop(1).foldRight(value) { key, child -> JsonNodeFactory.instance.objectNode().set<JsonNode>(key, child) }
+            return set(jsonObject, property, name, nested, generator)
+        }
+
+        if (value == null) {
+            property?.let { remove(it) }
+        } else {
+            set(jsonObject, property, name, value, generator)
+        }
+    }
+
+    private fun set(jsonObject: JsonObject, property: JsonProperty?, name: String, value: JsonNode, generator: JsonElementGenerator) {
+        val text = MirrordConfigFormat.JSON.writeValue(value)
+        val oldValue = property?.value
+        val added: PsiElement = when {
+            oldValue != null -> oldValue.replace(generator.createValue<JsonValue>(text))
+            property != null -> property.replace(generator.createProperty(name, text))
+            else -> JsonPsiUtil.addProperty(jsonObject, generator.createProperty(name, text), false)
+        }
+        CodeStyleManager.getInstance(jsonObject.project).reformatNewlyAddedElement(added.parent.node, added.node)
+    }
+
+    /**
+     * Removes the property together with its comma.
+     */
+    private fun remove(property: JsonProperty) {
+        val comma = PsiTreeUtil.skipWhitespacesAndCommentsForward(property)?.takeIf { it.node.elementType == JsonElementTypes.COMMA }
+            ?: PsiTreeUtil.skipWhitespacesAndCommentsBackward(property)?.takeIf { it.node.elementType == JsonElementTypes.COMMA }
+        comma?.delete()
+        property.delete()
+    }
+}
@@ -0,0 +1,72 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.JsonNode
+import com.fasterxml.jackson.databind.ObjectMapper
+import com.fasterxml.jackson.dataformat.yaml.YAMLFactory
+import java.io.File
+import java.nio.file.Path
+import java.nio.file.Paths
+
+/**
+ * Overrides `kube_context` from the mirrord config.
+ */
+const val KUBE_CONTEXT_ENV_NAME = "MIRRORD_KUBE_CONTEXT"
+
+/**
+ * Contexts found in the user's kubeconfig.
+ *
+ * @param current the `current-context` from the kubeconfig, null if not set
+ */
+data class KubeContexts(val contexts: List<String>, val current: String?)
+
+/**
+ * Reads the local kubeconfig, the same way `kubectl` does.
+ */
+object MirrordKubeConfig {
+    private val mapper = ObjectMapper(YAMLFactory())
+
+    /**
+     * Files listed in the `KUBECONFIG` variable, or the default `~/.kube/config`.
+     */
+    fun kubeConfigFiles(): List<Path> {
+        return System.getenv("KUBECONFIG")
+            ?.split(File.pathSeparator)
+            ?.filter { it.isNotBlank() }
+            ?.map { Paths.get(it) }
+            ?.ifEmpty { null }
+            ?: listOf(Paths.get(System.getProperty("user.home"), ".kube", "config"))
+    }
+
+    /**
+     * Merges contexts from all kubeconfig files.
+     * The first file that sets `current-context` wins, like in `kubectl`.
+     * Files that are missing or cannot be parsed are skipped.
+     * Reads the files on every call, [MirrordKubeConfigCache] keeps the result.
+     */
+    fun readContexts(files: List<Path> = kubeConfigFiles()): KubeContexts {
+        val contexts = LinkedHashSet<String>()
+        var current: String? = null
+
+        files
+            .map { it.toFile() }
+            .filter { it.isFile }
+            .forEach { file ->
+                val root: JsonNode = try {
+                    mapper.readTree(file) ?: return@forEach
+                } catch (e: Exception) {
+                    MirrordLogger.logger.debug("failed to parse kubeconfig $file", e)
+                    return@forEach
+                }
+
+                root.path("contexts").forEach { context ->
+                    context.path("name").textValue()?.let { contexts.add(it) }
+                }
+
+                if (current == null) {
+                    current = root.path("current-context").textValue()?.takeIf { it.isNotEmpty() }
+                }
+            }
+
+        return KubeContexts(contexts.toList(), current)
+    }
+}
@@ -0,0 +1,116 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.util.Disposer
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.openapi.vfs.AsyncFileListener
+import com.intellij.openapi.vfs.LocalFileSystem
+import com.intellij.openapi.vfs.VirtualFileManager
+import com.intellij.openapi.vfs.newvfs.events.VFileEvent
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicInteger
+
+/**
+ * Caches the contexts read from the kubeconfig, so that the dropdown does not read it from the disk every time it opens.
+ *
+ * The kubeconfig files are watched in the VFS, the cache is invalidated when any of them is changed, moved or deleted.
+ * The contexts are then read again in the background, so that the dropdown does not wait for the disk on the event dispatch thread.
+ */
+class MirrordKubeConfigCache(private val service: MirrordProjectService) : Disposable {
+    @Volatile
+    private var contexts: KubeContexts? = null
+
+    /**
+     * Incremented when the cache is invalidated, so that contexts read before are not cached.
+     */
+    private val generation = AtomicInteger()
+
+    /**
+     * Set while the contexts are read in the background.
+     */
+    private val loading = AtomicBoolean(false)
+
+    /**
+     * Kubeconfig files watched for external changes, replaced whenever the contexts are read.
+     */
+    @Volatile
+    private var watchRequests: Set<LocalFileSystem.WatchRequest> = emptySet()
+
+    /**
+     * Invalidates the cached contexts when a kubeconfig file is changed, moved or deleted.
+     */
+    private inner class KubeConfigWatch : AsyncFileListener {
+        override fun prepareChange(events: MutableList<out VFileEvent>): AsyncFileListener.ChangeApplier? {
+            val kubeConfigPaths = MirrordKubeConfig.kubeConfigFiles().map { FileUtil.toSystemIndependentName(it.toString()) }.toSet()
+            if (events.none { kubeConfigPaths.contains(it.path) }) {
+                return null
+            }
+
+            return object : AsyncFileListener.ChangeApplier {
+                override fun afterVfsChange() {
+                    generation.incrementAndGet()
+                    contexts = null
+                    loadInBackground()
+                }
+            }
+        }
+    }
+
+    init {
+        Disposer.register(service, this)
+        VirtualFileManager.getInstance().addAsyncFileListener(KubeConfigWatch(), this)
+        loadInBackground()
+    }
+
+    /**
+     * @return the cached contexts, read from the kubeconfig if they are not cached yet
+     */
+    fun readContexts(): KubeContexts {
+        contexts?.let { return it }
+
+        val readGeneration = generation.get()
+        val files = MirrordKubeConfig.kubeConfigFiles()
+        val fileSystem = LocalFileSystem.getInstance()
+        watchRequests = fileSystem.replaceWatchedRoots(watchRequests, null, files.map { it.toString() })
+        // The files must be in the VFS to get the events about their changes.
+        // Refreshed asynchronously, the contexts are read from the disk without waiting for the VFS.
+        fileSystem.refreshNioFiles(files, true, false, null)
+
+        return MirrordKubeConfig.readContexts(files).also {
+            if (generation.get() == readGeneration) {
+                contexts = it
+            }
+        }
+    }
+
+    /**
+     * Unlike [readContexts], does not read the kubeconfig on the calling thread, meant for the event dispatch thread.
+     *
+     * @return the cached contexts, null if they are not cached yet, in which case they are read in the background
+     */
+    fun cachedContexts(): KubeContexts? {
+        contexts?.let { return it }
+        loadInBackground()
+        return null
+    }
+
+    private fun loadInBackground() {
+        if (!loading.compareAndSet(false, true)) {
+            return
+        }
+
+        ApplicationManager.getApplication().executeOnPooledThread {
+            try {
+                readContexts()
+            } finally {
+                loading.set(false)
+            }
+        }
+    }
+
+    override fun dispose() {
+        LocalFileSystem.getInstance().removeWatchedRoots(watchRequests)
+        contexts = null
+    }
+}
@@ -0,0 +1,142 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.actionSystem.ActionUpdateThread
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.actionSystem.LangDataKeys
+import com.intellij.openapi.application.WriteAction
+import com.intellij.openapi.components.service
+import com.intellij.openapi.fileEditor.FileEditorManager
+import com.intellij.openapi.project.DumbAwareAction
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.ComboBox
+import com.intellij.openapi.ui.DialogWrapper
+import com.intellij.openapi.ui.ValidationInfo
+import com.intellij.openapi.vfs.VirtualFile
+import com.intellij.ui.EnumComboBoxModel
+import com.intellij.ui.components.JBCheckBox
+import com.intellij.ui.components.JBLabel
+import com.intellij.ui.components.JBTextField
+import com.intellij.util.ui.FormBuilder
+import com.intellij.util.ui.UIUtil
+import icons.MirrordIcons
+import java.io.IOException
+import javax.swing.JComponent
+
+/**
+ * "New > mirrord Config" action, creates a config file from one of the [MirrordConfigTemplate]s.
+ * The file is created in the directory selected in the project view, or in the `.mirrord` directory.
+ */
+class MirrordNewConfigAction : DumbAwareAction("mirrord Config", "Create a mirrord config file from a template", MirrordIcons.enabled) {
+    override fun getActionUpdateThread(): ActionUpdateThread = ActionUpdateThread.BGT
+
+    override fun update(e: AnActionEvent) {
+        e.presentation.isEnabledAndVisible = e.project != null
+    }
+
+    override fun actionPerformed(e: AnActionEvent) {
+        val project = e.project ?: return
+        val service = project.service<MirrordProjectService>()
+        val directory = e.getData(LangDataKeys.IDE_VIEW)?.directories?.singleOrNull()?.virtualFile
+
+        val dialog = MirrordNewConfigDialog(project, directory)
+        if (!dialog.showAndGet()) {
+            return
+        }
+
+        val config = try {
+            WriteAction.compute<VirtualFile, Exception> {
+                service.configApi.createConfig(directory, dialog.fileName(), dialog.template().render(dialog.format()))
+            }
+        } catch (ex: InvalidProjectException) {
+            service.notifier.notifyRichError(ex.richMessage)
+            return
+        } catch (ex: IOException) {
+            service.notifier.notifyRichError("failed to create the mirrord config: ${ex.message}")
+            return
+        }
+
+        if (dialog.makeActive()) {
+            service.activeConfig = config
+        }
+        FileEditorManager.getInstance(project).openFile(config, true)
+    }
+}
+
+/**
+ * Asks for the template, format and name of a new mirrord config.
+ *
+ * @param directory where the config will be created, null for the `.mirrord` directory
+ */
+private class MirrordNewConfigDialog(private val project: Project, private val directory: VirtualFile?) : DialogWrapper(project) {
+    private val template = ComboBox(EnumComboBoxModel(MirrordConfigTemplate::class.java))
+
+    private val description = JBLabel().apply {
+        componentStyle = UIUtil.ComponentStyle.SMALL
+        fontColor = UIUtil.FontColor.BRIGHTER
+    }
+
+    private val format = ComboBox(EnumComboBoxModel(MirrordConfigFormat::class.java))
+
+    private val name = JBTextField("mirrord")
+
+    private val makeActive = JBCheckBox("Make it the active config")
+
+    init {
+        title = "New mirrord Config"
+        template.addActionListener { updateDescription() }
+        updateDescription()
+        init()
+    }
+
+    private fun updateDescription() {
+        description.text = template().description
+    }
+
+    override fun createCenterPanel(): JComponent {
+        return FormBuilder
+            .createFormBuilder()
+            .addLabeledComponent("Template:", template)
+            .addComponentToRightColumn(description)
+            .addLabeledComponent("Format:", format)
+            .addLabeledComponent("File name:", name)
+            .addComponent(makeActive)
+            .panel
+    }
+
+    override fun getPreferredFocusedComponent(): JComponent = name
+
+    fun template(): MirrordConfigTemplate = template.selectedItem as MirrordConfigTemplate
+
+    fun format(): MirrordConfigFormat = format.selectedItem as MirrordConfigFormat
+
+    /**
+     * The name with the extension of the selected format, the extension is added if the user did not type it.
+     */
+    fun fileName(): String {
+        val base = name.text.trim().removeSuffix(".${format().extension}")
+        return "$base.${format().extension}"
+    }
+
+    fun makeActive(): Boolean = makeActive.isSelected
+
+    override fun doValidate(): ValidationInfo? {
+        val base = name.text.trim()
+        if (base.isEmpty() || base.contains('/') || base.contains('\\')) {
+            return ValidationInfo("Enter a file name", name)
+        }
+
+        val parent = try {
+            directory ?: project.service<MirrordProjectService>().configApi.getMirrordDir()
+        } catch (e: InvalidProjectException) {
+            return ValidationInfo(e.richMessage)
+        }
+        if (directory != null && !directory.path.contains("mirrord") && !base.contains("mirrord")) {
+            return ValidationInfo("The file name must contain \"mirrord\" to be recognized as a mirrord config", name)
+        }
+        if (parent?.findChild(fileName()) != null) {
+            return ValidationInfo("${fileName()} already exists", name)
+        }
+
+        return null
+    }
+}
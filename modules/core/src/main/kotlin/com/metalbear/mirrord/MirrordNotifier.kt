@@ -114,9 +114,17 @@ class MirrordNotifier(private val service: MirrordProjectService) {
         }
     }
 
-    fun notifyRichError(message: String) {
+    /**
+     * @param help displayed below the message
+     * @param showDetails if given, the notification has a "Show details" action that calls it
+     */
+    fun notifyRichError(message: String, help: String? = null, showDetails: (() -> Unit)? = null) {
         ApplicationManager.getApplication().invokeLater {
-            notification(message, NotificationType.ERROR)
+            val content = help?.let { "$message<br>$it" } ?: message
+            notification(content, NotificationType.ERROR)
+                .apply {
+                    showDetails?.let { show -> withAction("Show details") { _, _ -> show() } }
+                }
                 .withAction("Get support on Discord") { _, n ->
                     BrowserUtil.browse("https://discord.gg/metalbear")
                     n.expire()
@@ -0,0 +1,295 @@
+package com.metalbear.mirrord
+
+import com.google.gson.annotations.SerializedName
+import com.intellij.notification.NotificationType
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.components.service
+import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.progress.ProgressIndicator
+import com.intellij.openapi.progress.Task
+import com.intellij.openapi.util.SystemInfo
+import com.intellij.util.EventDispatcher
+import com.intellij.util.text.VersionComparatorUtil
+import java.time.Duration
+import java.time.LocalDate
+import java.time.format.DateTimeParseException
+import java.time.temporal.ChronoUnit
+import java.util.EventListener
+import java.util.concurrent.atomic.AtomicBoolean
+
+/**
+ * How many days before the license expiry the user is warned.
+ */
+private const val LICENSE_EXPIRY_WARNING_DAYS = 14L
+
+/**
+ * Output of `mirrord operator status -o json`, the `MirrordOperator` resource.
+ */
+data class MirrordOperatorStatus(val spec: Spec?, val status: Status?) {
+    data class Spec(
+        @SerializedName("operator_version") val operatorVersion: String?,
+        val license: License?
+    )
+
+    /**
+     * @param expireAt expiry date, e.g. `2024-12-31`
+     * @param maxSeats how many users can use the operator each month, null if not limited
+     */
+    data class License(
+        val name: String?,
+        val organization: String?,
+        @SerializedName("expire_at") val expireAt: String?,
+        @SerializedName("max_seats") val maxSeats: Int?
+    ) {
+        /**
+         * null if the license has no expiry date or it could not be parsed.
+         */
+        val expiryDate: LocalDate?
+            get() = expireAt?.let {
+                try {
+                    LocalDate.parse(it.take(10))
+                } catch (e: DateTimeParseException) {
+                    MirrordLogger.logger.debug("failed to parse operator license expiry date $it", e)
+                    null
+                }
+            }
+
+        /**
+         * Negative if the license has already expired, null if the expiry date is not known.
+         */
+        val daysUntilExpiry: Long?
+            get() = expiryDate?.let { ChronoUnit.DAYS.between(LocalDate.now(), it) }
+
+        /**
+         * Whether the user should be warned about the expiry.
+         */
+        val expiresSoon: Boolean
+            get() = daysUntilExpiry?.let { it < LICENSE_EXPIRY_WARNING_DAYS } ?: false
+    }
+
+    data class Status(val sessions: List<Session>?, val statistics: Statistics?)
+
+    /**
+     * @param dailyActiveUsers users of the operator in the last day
+     * @param monthlyActiveUsers users of the operator in the last month, these take the license seats
+     */
+    data class Statistics(
+        @SerializedName("dau") val dailyActiveUsers: Int?,
+        @SerializedName("mau") val monthlyActiveUsers: Int?
+    )
+
+    /**
+     * @param user identity of the user that started the session, e.g. `k8s-user/local-user@hostname`
+     * @param lockedPorts ports stolen by this session, as `[port, kind, http_filter]` tuples
+     */
+    data class Session(
+        val id: String?,
+        @SerializedName("duration_secs") val durationSecs: Long?,
+        val user: String?,
+        val target: String?,
+        val namespace: String?,
+        @SerializedName("locked_ports") val lockedPorts: List<List<Any?>>?
+    ) {
+        val age: Duration?
+            get() = durationSecs?.let { Duration.ofSeconds(it) }
+
+        /**
+         * E.g. `80 (steal, header: x-user: me), 8080 (steal)`.
+         */
+        fun lockedPortsDescription(): String {
+            return lockedPorts.orEmpty().joinToString(", ") { lock ->
+                val port = (lock.getOrNull(0) as? Number)?.toInt() ?: lock.getOrNull(0)
+                val details = lock.drop(1).filterNotNull().joinToString(", ")
+                if (details.isEmpty()) "$port" else "$port ($details)"
+            }
+        }
+    }
+
+    val sessions: List<Session>
+        get() = status?.sessions.orEmpty()
+
+    /**
+     * The mirrord binary warns when it is older than the operator, newer features of the operator may not work with it.
+     *
+     * @param localVersion version of the local mirrord binary, null if not known
+     * @return null if either version is not known
+     */
+    fun isCompatibleWith(localVersion: String?): Boolean? {
+        val operatorVersion = spec?.operatorVersion ?: return null
+        localVersion ?: return null
+        return VersionComparatorUtil.compare(localVersion, operatorVersion) >= 0
+    }
+}
+
+/**
+ * Fetches the status of the mirrord operator and manages its sessions.
+ * The status is refreshed when the user asks for it, and whenever mirrord runs with the operator.
+ */
+class MirrordOperatorManager(private val service: MirrordProjectService) {
+    fun interface Listener : EventListener {
+        /**
+         * Called from an arbitrary thread whenever the status is refreshed or the refresh fails.
+         */
+        fun operatorStatusChanged()
+    }
+
+    private val dispatcher = EventDispatcher.create(Listener::class.java)
+
+    /**
+     * The last fetched status, null if it was never fetched or the last refresh failed.
+     */
+    @Volatile
+    var status: MirrordOperatorStatus? = null
+        private set
+
+    /**
+     * Why the last refresh failed, null if it did not fail.
+     */
+    @Volatile
+    var error: String? = null
+        private set
+
+    /**
+     * Set while the refresh is running, so that refreshes do not pile up.
+     */
+    private val refreshing = AtomicBoolean(false)
+
+    val isRefreshing: Boolean
+        get() = refreshing.get()
+
+    /**
+     * License expiry date the user was already warned about.
+     */
+    @Volatile
+    private var warnedExpiry: LocalDate? = null
+
+    fun addListener(listener: Listener, parentDisposable: Disposable) {
+        dispatcher.addListener(listener, parentDisposable)
+    }
+
+    private fun fireChanged() {
+        dispatcher.multicaster.operatorStatusChanged()
+    }
+
+    private fun cliPath(): String {
+        if (SystemInfo.isWindows) {
+            throw MirrordError("can't use on Windows without WSL")
+        }
+
+        return service<MirrordBinaryManager>().getBinary("operator", null, service.project)
+    }
+
+    /**
+     * Runs [body] in the background with the mirrord binary and the active or default config.
+     * Errors are displayed as notifications, the status is refreshed afterwards.
+     */
+    private fun runInBackground(title: String, body: (MirrordApi, String, String?, ProgressIndicator) -> Unit) {
+        object : Task.Backgroundable(service.project, title, true) {
+            override fun run(indicator: ProgressIndicator) {
+                val cli = cliPath()
+                val configFile = service.configApi.getConfigPath(null)
+                body(service.mirrordApi(null), cli, configFile, indicator)
+            }
+
+            override fun onThrowable(error: Throwable) {
+                when (error) {
+                    is MirrordError -> error.showHelp(service.project)
+                    is ProcessCanceledException -> {}
+                    else -> service.notifier.notifySimple("$title failed: ${error.message}", NotificationType.ERROR)
+                }
+            }
+
+            override fun onFinished() {
+                refresh()
+            }
+        }.queue()
+    }
+
+    /**
+     * Fetches the operator status in the background.
+     * Does nothing if the status is already being fetched.
+     */
+    fun refresh() {
+        if (!refreshing.compareAndSet(false, true)) {
+            return
+        }
+        fireChanged()
+
+        object : Task.Backgroundable(service.project, "Fetching mirrord operator status", true) {
+            override fun run(indicator: ProgressIndicator) {
+                try {
+                    val cli = cliPath()
+                    val configFile = service.configApi.getConfigPath(null)
+                    val fetched = service.mirrordApi(null).operatorStatus(cli, configFile, indicator)
+                    status = fetched
+                    error = null
+                    warnAboutLicenseExpiry(fetched)
+                } catch (e: MirrordError) {
+                    status = null
+                    error = e.richMessage
+                } catch (e: ProcessCanceledException) {
+                    throw e
+                } catch (e: Exception) {
+                    MirrordLogger.logger.warn("failed to fetch mirrord operator status", e)
+                    status = null
+                    error = e.message ?: e.toString()
+                }
+            }
+
+            override fun onFinished() {
+                refreshing.set(false)
+                fireChanged()
+            }
+        }.queue()
+    }
+
+    /**
+     * Warns once per expiry date when the license expires in less than [LICENSE_EXPIRY_WARNING_DAYS] days.
+     */
+    private fun warnAboutLicenseExpiry(status: MirrordOperatorStatus) {
+        val license = status.spec?.license ?: return
+        val expiry = license.expiryDate ?: return
+        val daysLeft = license.daysUntilExpiry ?: return
+        if (!license.expiresSoon || warnedExpiry == expiry) {
+            return
+        }
+        warnedExpiry = expiry
+
+        val message = if (daysLeft < 0) {
+            "mirrord operator license expired on $expiry"
+        } else {
+            "mirrord operator license expires in $daysLeft days, on $expiry"
+        }
+        service
+            .notifier
+            .notification(message, NotificationType.WARNING)
+            .withAction("Show Operator Status") { _, n ->
+                n.expire()
+                MirrordToolWindowFactory.showTab(service.project, MirrordOperatorStatusPanel.TITLE)
+            }
+            .withDontShowAgain(MirrordSettingsState.NotificationId.OPERATOR_LICENSE_EXPIRY)
+            .fire()
+    }
+
+    /**
+     * Kills a single operator session in the background.
+     * The operator decides whether the user is allowed to kill it, its error is displayed if not.
+     */
+    fun killSession(session: MirrordOperatorStatus.Session) {
+        val id = session.id ?: return
+        runInBackground("Killing mirrord operator session") { api, cli, configFile, indicator ->
+            api.killOperatorSessions(cli, configFile, id, indicator)
+            service.notifier.notifySimple("Operator session of ${session.user} on ${session.target} was killed", NotificationType.INFORMATION)
+        }
+    }
+
+    /**
+     * Kills all operator sessions in the background, the operator rejects it if the user is not allowed to.
+     */
+    fun killAllSessions() {
+        runInBackground("Killing all mirrord operator sessions") { api, cli, configFile, indicator ->
+            api.killOperatorSessions(cli, configFile, null, indicator)
+            service.notifier.notifySimple("All operator sessions were killed", NotificationType.INFORMATION)
+        }
+    }
+}
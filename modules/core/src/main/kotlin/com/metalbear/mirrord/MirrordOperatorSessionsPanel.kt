@@ -0,0 +1,165 @@
+package com.metalbear.mirrord
+
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.actionSystem.ActionManager
+import com.intellij.openapi.actionSystem.ActionUpdateThread
+import com.intellij.openapi.actionSystem.AnAction
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.actionSystem.DefaultActionGroup
+import com.intellij.openapi.actionSystem.Separator
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.Messages
+import com.intellij.openapi.ui.SimpleToolWindowPanel
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.table.TableView
+import com.intellij.util.Alarm
+import com.intellij.util.ui.ColumnInfo
+import com.intellij.util.ui.ListTableModel
+import java.time.Duration
+
+private fun Duration.formatAge(): String = when {
+    toDays() > 0 -> "${toDays()}d ${toHoursPart()}h"
+    toHours() > 0 -> "${toHours()}h ${toMinutesPart()}m"
+    toMinutes() > 0 -> "${toMinutes()}m ${toSecondsPart()}s"
+    else -> "${seconds}s"
+}
+
+/**
+ * Lists the active sessions of the mirrord operator and lets the user kill them.
+ */
+class MirrordOperatorSessionsPanel(private val project: Project, parentDisposable: Disposable) : SimpleToolWindowPanel(true, true) {
+    companion object {
+        const val TITLE = "Operator Sessions"
+    }
+
+    private val manager = project.service<MirrordProjectService>().operator
+
+    private val columns: Array<ColumnInfo<MirrordOperatorStatus.Session, String>> = arrayOf(
+        object : ColumnInfo<MirrordOperatorStatus.Session, String>("Owner") {
+            override fun valueOf(item: MirrordOperatorStatus.Session): String = item.user ?: "unknown"
+        },
+        object : ColumnInfo<MirrordOperatorStatus.Session, String>("Target") {
+            override fun valueOf(item: MirrordOperatorStatus.Session): String {
+                val target = item.target ?: "targetless"
+                return item.namespace?.let { "$it/$target" } ?: target
+            }
+        },
+        object : ColumnInfo<MirrordOperatorStatus.Session, String>("Age") {
+            override fun valueOf(item: MirrordOperatorStatus.Session): String = item.age?.formatAge().orEmpty()
+        },
+        object : ColumnInfo<MirrordOperatorStatus.Session, String>("Locked Ports") {
+            override fun valueOf(item: MirrordOperatorStatus.Session): String = item.lockedPortsDescription()
+        }
+    )
+
+    private val tableModel = ListTableModel<MirrordOperatorStatus.Session>(*columns)
+
+    private val table = TableView(tableModel)
+
+    /**
+     * Batches updates, the manager reports both the start and the end of a refresh.
+     */
+    private val updateAlarm = Alarm(Alarm.ThreadToUse.SWING_THREAD, parentDisposable)
+
+    private inner class RefreshAction : AnAction("Refresh", null, AllIcons.Actions.Refresh), DumbAware {
+        override fun getActionUpdateThread(): ActionUpdateThread = ActionUpdateThread.BGT
+
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = !manager.isRefreshing
+        }
+
+        override fun actionPerformed(e: AnActionEvent) {
+            manager.refresh()
+        }
+    }
+
+    private inner class KillSessionAction : AnAction("Kill Session", "Kill the selected session, requires permission to manage the sessions of other users if it is not yours", AllIcons.Actions.Suspend), DumbAware {
+        override fun getActionUpdateThread(): ActionUpdateThread = ActionUpdateThread.EDT
+
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = table.selectedObject?.id != null
+        }
+
+        override fun actionPerformed(e: AnActionEvent) {
+            val session = table.selectedObject ?: return
+            val confirmed = Messages.showYesNoDialog(
+                project,
+                "Kill the operator session on ${session.target ?: "targetless"}? Processes using it will lose the connection to the cluster.",
+                "Kill mirrord Operator Session",
+                Messages.getWarningIcon()
+            )
+            if (confirmed == Messages.YES) {
+                manager.killSession(session)
+            }
+        }
+    }
+
+    private inner class KillAllSessionsAction : AnAction("Kill All Sessions", "Kill all sessions, requires permission to manage the sessions of other users", AllIcons.Actions.Cancel), DumbAware {
+        override fun getActionUpdateThread(): ActionUpdateThread = ActionUpdateThread.EDT
+
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = tableModel.items.isNotEmpty()
+        }
+
+        override fun actionPerformed(e: AnActionEvent) {
+            val confirmed = Messages.showYesNoDialog(
+                project,
+                "Kill all ${tableModel.items.size} operator sessions, including the sessions of other users?",
+                "Kill All mirrord Operator Sessions",
+                Messages.getWarningIcon()
+            )
+            if (confirmed == Messages.YES) {
+                manager.killAllSessions()
+            }
+        }
+    }
+
+    init {
+        val actions = DefaultActionGroup(
+            RefreshAction(),
+            Separator.getInstance(),
+            KillSessionAction(),
+            KillAllSessionsAction()
+        )
+        val toolbar = ActionManager
+            .getInstance()
+            .createActionToolbar("MirrordOperatorSessions", actions, true)
+        toolbar.targetComponent = this
+        setToolbar(toolbar.component)
+        setContent(ScrollPaneFactory.createScrollPane(table))
+
+        manager.addListener({ scheduleUpdate() }, parentDisposable)
+        update()
+    }
+
+    private fun scheduleUpdate() {
+        if (updateAlarm.isDisposed) {
+            return
+        }
+
+        updateAlarm.cancelAllRequests()
+        updateAlarm.addRequest({ update() }, 100, ModalityState.any())
+    }
+
+    private fun update() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+
+        val status = manager.status
+        val error = manager.error
+        table.emptyText.text = when {
+            manager.isRefreshing -> "Fetching the operator status..."
+            error != null -> "Failed to fetch the operator status: $error"
+            status == null -> "Refresh to list the sessions of the mirrord operator"
+            else -> "No active operator sessions"
+        }
+
+        val selectedId = table.selectedObject?.id
+        tableModel.items = status?.sessions.orEmpty()
+        tableModel.items.firstOrNull { it.id != null && it.id == selectedId }?.let { table.setSelection(listOf(it)) }
+    }
+}
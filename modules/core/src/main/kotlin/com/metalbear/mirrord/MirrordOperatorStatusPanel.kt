@@ -0,0 +1,153 @@
+package com.metalbear.mirrord
+
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.actionSystem.ActionManager
+import com.intellij.openapi.actionSystem.ActionUpdateThread
+import com.intellij.openapi.actionSystem.AnAction
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.actionSystem.DefaultActionGroup
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.SimpleToolWindowPanel
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.components.JBLabel
+import com.intellij.util.Alarm
+import com.intellij.util.ui.FormBuilder
+import com.intellij.util.ui.JBUI
+import java.awt.BorderLayout
+import javax.swing.JPanel
+
+/**
+ * Displays the version and license of the mirrord operator, from `mirrord operator status`.
+ */
+class MirrordOperatorStatusPanel(project: Project, parentDisposable: Disposable) : SimpleToolWindowPanel(true, true) {
+    companion object {
+        const val TITLE = "Operator"
+    }
+
+    private val manager = project.service<MirrordProjectService>().operator
+
+    private val state = JBLabel()
+
+    private val operatorVersion = JBLabel()
+
+    private val localVersion = JBLabel()
+
+    private val compatibility = JBLabel()
+
+    private val license = JBLabel()
+
+    private val expiry = JBLabel()
+
+    private val seats = JBLabel()
+
+    private val dailyUsers = JBLabel()
+
+    /**
+     * Batches updates, the manager reports both the start and the end of a refresh.
+     */
+    private val updateAlarm = Alarm(Alarm.ThreadToUse.SWING_THREAD, parentDisposable)
+
+    private inner class RefreshAction : AnAction("Refresh", null, AllIcons.Actions.Refresh), DumbAware {
+        override fun getActionUpdateThread(): ActionUpdateThread = ActionUpdateThread.BGT
+
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = !manager.isRefreshing
+        }
+
+        override fun actionPerformed(e: AnActionEvent) {
+            manager.refresh()
+        }
+    }
+
+    init {
+        val toolbar = ActionManager
+            .getInstance()
+            .createActionToolbar("MirrordOperatorStatus", DefaultActionGroup(RefreshAction()), true)
+        toolbar.targetComponent = this
+        setToolbar(toolbar.component)
+
+        val form = FormBuilder
+            .createFormBuilder()
+            .addComponent(state)
+            .addSeparator()
+            .addLabeledComponent("Operator version:", operatorVersion)
+            .addLabeledComponent("Local mirrord version:", localVersion)
+            .addLabeledComponent("Compatibility:", compatibility)
+            .addSeparator()
+            .addLabeledComponent("License:", license)
+            .addLabeledComponent("Expires:", expiry)
+            .addLabeledComponent("Seats used this month:", seats)
+            .addLabeledComponent("Users today:", dailyUsers)
+            .panel
+            .apply { border = JBUI.Borders.empty(10) }
+        setContent(ScrollPaneFactory.createScrollPane(JPanel(BorderLayout()).apply { add(form, BorderLayout.NORTH) }, true))
+
+        manager.addListener({ scheduleUpdate() }, parentDisposable)
+        update()
+    }
+
+    private fun scheduleUpdate() {
+        if (updateAlarm.isDisposed) {
+            return
+        }
+
+        updateAlarm.cancelAllRequests()
+        updateAlarm.addRequest({ update() }, 100, ModalityState.any())
+    }
+
+    private fun update() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+
+        val status = manager.status
+        val error = manager.error
+        state.text = when {
+            manager.isRefreshing -> "Fetching the operator status..."
+            error != null -> "Failed to fetch the operator status: $error"
+            status == null -> "Refresh to fetch the status of the mirrord operator"
+            else -> "Status of the mirrord operator in the current cluster context"
+        }
+        state.icon = if (error != null && !manager.isRefreshing) AllIcons.General.Error else null
+
+        val binaryVersion = service<MirrordBinaryManager>().usedVersion
+        operatorVersion.text = status?.spec?.operatorVersion ?: "-"
+        localVersion.text = binaryVersion ?: "-"
+        when (status?.isCompatibleWith(binaryVersion)) {
+            true -> {
+                compatibility.text = "Compatible"
+                compatibility.icon = null
+            }
+            false -> {
+                compatibility.text = "Local mirrord is older than the operator, update mirrord to use all operator features"
+                compatibility.icon = AllIcons.General.Warning
+            }
+            null -> {
+                compatibility.text = "-"
+                compatibility.icon = null
+            }
+        }
+
+        val licenseInfo = status?.spec?.license
+        license.text = licenseInfo
+            ?.let { listOfNotNull(it.name, it.organization?.let { org -> "($org)" }).joinToString(" ") }
+            ?.ifEmpty { null }
+            ?: "-"
+
+        val daysLeft = licenseInfo?.daysUntilExpiry
+        expiry.text = when {
+            daysLeft == null -> licenseInfo?.expireAt ?: "-"
+            daysLeft < 0 -> "${licenseInfo?.expiryDate} (expired)"
+            else -> "${licenseInfo?.expiryDate} (in $daysLeft days)"
+        }
+        expiry.icon = if (licenseInfo?.expiresSoon == true) AllIcons.General.Warning else null
+
+        val statistics = status?.status?.statistics
+        val usedSeats = statistics?.monthlyActiveUsers?.toString() ?: "-"
+        seats.text = licenseInfo?.maxSeats?.let { "$usedSeats of $it" } ?: usedSeats
+        dailyUsers.text = statistics?.dailyActiveUsers?.toString() ?: "-"
+    }
+}
@@ -0,0 +1,166 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.CommonProgramRunConfigurationParameters
+import com.intellij.execution.configurations.ModuleBasedConfiguration
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.execution.util.ProgramParametersUtil
+import com.intellij.ide.macro.Macro
+import com.intellij.ide.macro.MacroManager
+import com.intellij.ide.macro.PromptingMacro
+import com.intellij.openapi.actionSystem.CommonDataKeys
+import com.intellij.openapi.actionSystem.DataContext
+import com.intellij.openapi.actionSystem.PlatformCoreDataKeys
+import com.intellij.openapi.actionSystem.impl.SimpleDataContext
+import com.intellij.openapi.application.ReadAction
+import com.intellij.openapi.components.PathMacroManager
+import com.intellij.openapi.components.service
+import com.intellij.openapi.module.Module
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.openapi.vfs.LocalFileSystem
+import java.nio.file.InvalidPathException
+import java.nio.file.Paths
+
+/**
+ * Expands IDE path macros in the mirrord config path set in a run configuration,
+ * so that shared run configurations work regardless of where the project is checked out.
+ */
+object MirrordPathMacros {
+    private val MACRO_REGEX = Regex("\\$([A-Za-z_][A-Za-z0-9_]*)\\$")
+
+    /**
+     * A macro at the beginning of the path, for example `$ProjectFileDir$` in `$ProjectFileDir$/.mirrord/mirrord.json`.
+     */
+    private val LEADING_MACRO_REGEX = Regex("^\\$[A-Za-z_][A-Za-z0-9_]*\\$")
+
+    /**
+     * Kept for run configurations created before all macros were supported, same as `$ProjectFileDir$`.
+     */
+    private const val PROJECT_PATH_MACRO = "ProjectPath"
+
+    /**
+     * Expands the macros in the path, for example `$ProjectFileDir$`, `$ModuleFileDir$`, `$ContentRoot$` or `$USER_HOME$`.
+     * Relative paths are resolved against the working directory of the run configuration, or the project directory.
+     *
+     * @param configuration run configuration being started, null if not known
+     * @throws MirrordError if a macro is unknown or cannot be evaluated for the run configuration
+     */
+    fun expandConfigPath(project: Project, configuration: RunConfigurationBase<*>?, path: String): String {
+        val module = (configuration as? ModuleBasedConfiguration<*, *>)?.configurationModule?.module
+        val baseDir = workingDirectory(project, configuration, module)
+
+        val unresolved = mutableListOf<String>()
+        val expanded = ReadAction.compute<String, RuntimeException> {
+            val dataContext = dataContext(project, module, baseDir)
+            val pathMacros = PathMacroManager.getInstance(module ?: project)
+
+            MACRO_REGEX.replace(path) { match ->
+                val name = match.groupValues[1]
+                val value = expandMacro(project, name, dataContext)
+                    ?: pathMacros.expandPath(match.value).takeIf { it != match.value }
+                value ?: match.value.also { unresolved.add(name) }
+            }
+        }
+
+        if (unresolved.isNotEmpty()) {
+            throw MirrordError(
+                "failed to expand ${unresolved.joinToString { "`\$$it\$`" }} in the mirrord config path `$path`",
+                "Macros such as \$ProjectFileDir\$, \$ModuleFileDir\$, \$ContentRoot\$ and \$USER_HOME\$ are supported. " +
+                    "Module macros require a run configuration with a module. Fix the path in the mirrord tab of the run configuration."
+            )
+        }
+
+        val expandedPath = try {
+            Paths.get(FileUtil.toSystemDependentName(expanded))
+        } catch (e: InvalidPathException) {
+            throw MirrordError("mirrord config path `$expanded` is invalid", e)
+        }
+        if (expandedPath.isAbsolute || baseDir == null) {
+            return expandedPath.normalize().toString()
+        }
+
+        return Paths.get(baseDir).resolve(expandedPath).normalize().toString()
+    }
+
+    /**
+     * Replaces the project and home directories in the path with macros, so that the path can be stored
+     * in a shared run configuration.
+     */
+    fun collapseConfigPath(project: Project, path: String): String {
+        return PathMacroManager.getInstance(project).collapsePath(FileUtil.toSystemIndependentName(path))
+    }
+
+    /**
+     * Points the config path set in a run configuration to the new location of the config.
+     * If the new location is still under the leading macro of the path (or under the working directory for a relative path),
+     * the macro (or the relative form) is kept, otherwise the new location is collapsed with [collapseConfigPath].
+     *
+     * @param path the path as set in the run configuration
+     * @param newLocation absolute path of the moved config
+     */
+    fun rebaseConfigPath(project: Project, configuration: RunConfigurationBase<*>?, path: String, newLocation: String): String {
+        val newPath = FileUtil.toSystemIndependentName(newLocation)
+        val prefix = LEADING_MACRO_REGEX.find(path)?.value ?: if (FileUtil.isAbsolute(path)) null else "."
+
+        val baseDir = prefix?.let {
+            try {
+                FileUtil.toSystemIndependentName(expandConfigPath(project, configuration, it))
+            } catch (e: MirrordError) {
+                null
+            }
+        }
+        val relative = baseDir?.takeIf { FileUtil.isAncestor(it, newPath, true) }?.let { FileUtil.getRelativePath(it, newPath, '/') }
+
+        return when {
+            relative == null -> collapseConfigPath(project, newPath)
+            prefix == "." -> relative
+            else -> "$prefix/$relative"
+        }
+    }
+
+    /**
+     * @return the expanded working directory of the run configuration, or the project directory
+     */
+    private fun workingDirectory(project: Project, configuration: RunConfigurationBase<*>?, module: Module?): String? {
+        val workingDir = (configuration as? CommonProgramRunConfigurationParameters)?.let {
+            try {
+                ProgramParametersUtil.getWorkingDir(it, project, module)
+            } catch (e: Exception) {
+                MirrordLogger.logger.debug("failed to expand the working directory of the run configuration", e)
+                null
+            }
+        }
+
+        return workingDir?.takeIf { it.isNotBlank() } ?: project.basePath
+    }
+
+    /**
+     * Context for the macros, file macros such as `$ContentRoot$` are evaluated for [baseDir].
+     */
+    private fun dataContext(project: Project, module: Module?, baseDir: String?): DataContext {
+        val builder = SimpleDataContext.builder().add(CommonDataKeys.PROJECT, project)
+        module?.let { builder.add(PlatformCoreDataKeys.MODULE, it) }
+        baseDir
+            ?.let { LocalFileSystem.getInstance().findFileByPath(FileUtil.toSystemIndependentName(it)) }
+            ?.let { builder.add(CommonDataKeys.VIRTUAL_FILE, it) }
+        return builder.build()
+    }
+
+    /**
+     * Macros that prompt the user are not evaluated, the config path is resolved without user interaction.
+     *
+     * @return null if there is no such macro or it has no value in this context
+     */
+    private fun expandMacro(project: Project, name: String, dataContext: DataContext): String? {
+        if (name == PROJECT_PATH_MACRO) {
+            return project.service<MirrordProjectService>().configApi.getProjectDir().canonicalPath
+        }
+
+        val macro = MacroManager.getInstance().macros.find { it.name == name && it !is PromptingMacro } ?: return null
+        return try {
+            macro.expand(dataContext)?.takeIf { it.isNotEmpty() }
+        } catch (e: Macro.ExecutionCancelledException) {
+            null
+        }
+    }
+}
@@ -35,6 +35,11 @@ object MirrordPathManager {
         return pluginDir().resolve(format)
     }
 
+    /**
+     * Directory with the cached config schemas, one subdirectory per mirrord version.
+     */
+    fun getSchemaDir(): Path = pluginDir().resolve("schema")
+
     /**
      * Get matching binary based on platform and architecture.
      */
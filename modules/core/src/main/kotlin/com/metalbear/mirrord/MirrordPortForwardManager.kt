@@ -0,0 +1,244 @@
+package com.metalbear.mirrord
+
+import com.google.gson.Gson
+import com.intellij.execution.process.OSProcessHandler
+import com.intellij.execution.process.ProcessEvent
+import com.intellij.execution.process.ProcessListener
+import com.intellij.execution.process.ProcessOutputType
+import com.intellij.notification.NotificationType
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.components.service
+import com.intellij.openapi.util.Key
+import com.intellij.openapi.util.SystemInfo
+import com.intellij.util.EventDispatcher
+import java.util.EventListener
+import java.util.concurrent.CopyOnWriteArrayList
+
+/**
+ * How many lines of the mirrord stderr are kept to display when the port forward fails.
+ */
+private const val MAX_STDERR_LINES = 20
+
+/**
+ * A single `mirrord port-forward -L local_port:remote_host:remote_port` mapping.
+ *
+ * @param remoteHost host or IP address, resolved in the cluster
+ * @param target target the traffic goes through, null to use the target from the config or run targetless
+ */
+class MirrordPortForward(val localPort: Int, val remoteHost: String, val remotePort: Int, val target: String?) {
+    enum class Status(val presentableName: String) {
+        STOPPED("Stopped"),
+        STARTING("Starting"),
+        RUNNING("Running"),
+        FAILED("Failed")
+    }
+
+    @Volatile
+    var status: Status = Status.STOPPED
+        internal set
+
+    /**
+     * Why the port forward failed, null if it did not fail.
+     */
+    @Volatile
+    var error: String? = null
+        internal set
+
+    @Volatile
+    internal var handler: OSProcessHandler? = null
+
+    /**
+     * Set when the user stops the port forward, so that the termination is not reported as a failure.
+     */
+    @Volatile
+    internal var stopRequested = false
+
+    val mapping: String
+        get() = "$localPort:$remoteHost:$remotePort"
+}
+
+/**
+ * Manages the `mirrord port-forward` processes in the project.
+ * Displayed in the mirrord tool window, all processes are stopped when the project is closed.
+ */
+class MirrordPortForwardManager(private val service: MirrordProjectService) : Disposable {
+    fun interface Listener : EventListener {
+        /**
+         * Called from an arbitrary thread whenever a port forward is added, removed or changes its status.
+         */
+        fun portForwardsChanged()
+    }
+
+    private val dispatcher = EventDispatcher.create(Listener::class.java)
+
+    private val _forwards: MutableList<MirrordPortForward> = CopyOnWriteArrayList()
+
+    val forwards: List<MirrordPortForward>
+        get() = _forwards.toList()
+
+    fun addListener(listener: Listener, parentDisposable: Disposable) {
+        dispatcher.addListener(listener, parentDisposable)
+    }
+
+    private fun fireChanged() {
+        dispatcher.multicaster.portForwardsChanged()
+    }
+
+    /**
+     * Adds the port forward and starts it.
+     */
+    fun add(forward: MirrordPortForward) {
+        _forwards.add(forward)
+        fireChanged()
+        start(forward)
+    }
+
+    /**
+     * Stops the port forward and removes it from the list.
+     */
+    fun remove(forward: MirrordPortForward) {
+        stop(forward)
+        _forwards.remove(forward)
+        fireChanged()
+    }
+
+    /**
+     * Starts the `mirrord port-forward` process in the background.
+     * Does nothing if the port forward is already starting or running.
+     */
+    fun start(forward: MirrordPortForward) {
+        if (forward.status == MirrordPortForward.Status.STARTING || forward.status == MirrordPortForward.Status.RUNNING) {
+            return
+        }
+
+        forward.status = MirrordPortForward.Status.STARTING
+        forward.error = null
+        forward.stopRequested = false
+        fireChanged()
+
+        ApplicationManager.getApplication().executeOnPooledThread {
+            try {
+                if (SystemInfo.isWindows) {
+                    throw MirrordError("port forwarding is not supported on Windows")
+                }
+
+                val cli = service<MirrordBinaryManager>().getBinary("port-forward", null, service.project)
+                val configFile = service.configApi.getConfigPath(null)
+                val commandLine = service
+                    .mirrordApi(null)
+                    .portForwardCommandLine(cli, forward.target, configFile, forward.mapping)
+
+                MirrordLogger.logger.info("starting mirrord port forward: ${commandLine.commandLineString}")
+                val handler = OSProcessHandler(commandLine)
+                handler.addProcessListener(PortForwardListener(forward))
+                forward.handler = handler
+                handler.startNotify()
+
+                if (forward.stopRequested) {
+                    handler.destroyProcess()
+                }
+            } catch (e: MirrordError) {
+                fail(forward, e.richMessage)
+                e.showHelp(service.project)
+            } catch (e: Throwable) {
+                MirrordLogger.logger.warn("failed to start mirrord port forward ${forward.mapping}", e)
+                fail(forward, e.message ?: e.toString())
+            }
+        }
+    }
+
+    /**
+     * Stops the `mirrord port-forward` process, if it is running.
+     */
+    fun stop(forward: MirrordPortForward) {
+        forward.stopRequested = true
+        forward.handler?.destroyProcess()
+    }
+
+    private fun fail(forward: MirrordPortForward, error: String) {
+        forward.handler = null
+        forward.status = MirrordPortForward.Status.FAILED
+        forward.error = error
+        fireChanged()
+    }
+
+    /**
+     * Follows the json progress of the `mirrord port-forward` process.
+     * The forwarding is ready when the top level task finishes successfully.
+     */
+    private inner class PortForwardListener(private val forward: MirrordPortForward) : ProcessListener {
+        private val gson = Gson()
+
+        private val stderr = ArrayDeque<String>()
+
+        override fun onTextAvailable(event: ProcessEvent, outputType: Key<*>) {
+            val line = event.text.trim()
+            if (line.isEmpty()) {
+                return
+            }
+
+            if (outputType == ProcessOutputType.STDERR) {
+                synchronized(stderr) {
+                    stderr.addLast(line)
+                    while (stderr.size > MAX_STDERR_LINES) {
+                        stderr.removeFirst()
+                    }
+                }
+                return
+            }
+
+            if (outputType != ProcessOutputType.STDOUT) {
+                return
+            }
+
+            val message = try {
+                gson.fromJson(line, Message::class.java)
+            } catch (e: Throwable) {
+                MirrordLogger.logger.debug("mirrord port forward ${forward.mapping}: $line")
+                return
+            } ?: return
+
+            when {
+                message.type == MessageType.FinishedTask && message.parent == null -> {
+                    if (message.success == true) {
+                        forward.status = MirrordPortForward.Status.RUNNING
+                        fireChanged()
+                    }
+                }
+
+                message.type == MessageType.Warning -> message.message?.let {
+                    service.notifier.notifySimple("Port forward ${forward.mapping}: $it", NotificationType.WARNING)
+                }
+
+                else -> {}
+            }
+        }
+
+        override fun processTerminated(event: ProcessEvent) {
+            forward.handler = null
+
+            if (forward.stopRequested) {
+                forward.status = MirrordPortForward.Status.STOPPED
+                fireChanged()
+                return
+            }
+
+            val output = synchronized(stderr) { stderr.joinToString("\n") }
+            val error = MirrordError.fromStdErr(output).richMessage.ifBlank { "mirrord exited with code ${event.exitCode}" }
+            fail(forward, error)
+            service
+                .notifier
+                .notification("Port forward ${forward.mapping} stopped: $error", NotificationType.ERROR)
+                .withAction("Restart") { _, n ->
+                    n.expire()
+                    start(forward)
+                }
+                .fire()
+        }
+    }
+
+    override fun dispose() {
+        _forwards.forEach { stop(it) }
+    }
+}
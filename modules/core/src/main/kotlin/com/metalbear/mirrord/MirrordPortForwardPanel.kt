@@ -0,0 +1,195 @@
+package com.metalbear.mirrord
+
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.actionSystem.ActionManager
+import com.intellij.openapi.actionSystem.ActionUpdateThread
+import com.intellij.openapi.actionSystem.AnAction
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.actionSystem.DefaultActionGroup
+import com.intellij.openapi.actionSystem.Separator
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.DialogWrapper
+import com.intellij.openapi.ui.SimpleToolWindowPanel
+import com.intellij.openapi.ui.ValidationInfo
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.components.JBTextField
+import com.intellij.ui.table.TableView
+import com.intellij.util.Alarm
+import com.intellij.util.ui.ColumnInfo
+import com.intellij.util.ui.FormBuilder
+import com.intellij.util.ui.ListTableModel
+import javax.swing.Icon
+import javax.swing.JComponent
+
+/**
+ * Displays the port forwards from [MirrordPortForwardManager] and lets the user add, start and stop them.
+ */
+class MirrordPortForwardPanel(private val project: Project, parentDisposable: Disposable) : SimpleToolWindowPanel(true, true) {
+    private val manager = project.service<MirrordProjectService>().portForwards
+
+    private val columns: Array<ColumnInfo<MirrordPortForward, String>> = arrayOf(
+        object : ColumnInfo<MirrordPortForward, String>("Local Port") {
+            override fun valueOf(item: MirrordPortForward): String = item.localPort.toString()
+        },
+        object : ColumnInfo<MirrordPortForward, String>("Remote Address") {
+            override fun valueOf(item: MirrordPortForward): String = "${item.remoteHost}:${item.remotePort}"
+        },
+        object : ColumnInfo<MirrordPortForward, String>("Target") {
+            override fun valueOf(item: MirrordPortForward): String = item.target ?: "from config or targetless"
+        },
+        object : ColumnInfo<MirrordPortForward, String>("Status") {
+            override fun valueOf(item: MirrordPortForward): String {
+                return item.error?.let { "${item.status.presentableName}: $it" } ?: item.status.presentableName
+            }
+        }
+    )
+
+    private val tableModel = ListTableModel<MirrordPortForward>(*columns)
+
+    private val table = TableView(tableModel).apply {
+        emptyText.text = "No port forwards, add one to forward a local port through mirrord"
+    }
+
+    /**
+     * Batches updates, status changes of many port forwards can come at once.
+     */
+    private val updateAlarm = Alarm(Alarm.ThreadToUse.SWING_THREAD, parentDisposable)
+
+    private inner class AddAction : AnAction("Add Port Forward...", null, AllIcons.General.Add), DumbAware {
+        override fun actionPerformed(e: AnActionEvent) {
+            val dialog = AddPortForwardDialog(project)
+            if (dialog.showAndGet()) {
+                manager.add(dialog.portForward())
+            }
+        }
+    }
+
+    /**
+     * Action on the selected port forward.
+     */
+    private inner class SelectionAction(
+        text: String,
+        icon: Icon,
+        private val isApplicable: (MirrordPortForward) -> Boolean,
+        private val perform: (MirrordPortForward) -> Unit
+    ) : AnAction(text, null, icon), DumbAware {
+        override fun getActionUpdateThread(): ActionUpdateThread = ActionUpdateThread.EDT
+
+        override fun update(e: AnActionEvent) {
+            e.presentation.isEnabled = table.selectedObjects.any(isApplicable)
+        }
+
+        override fun actionPerformed(e: AnActionEvent) {
+            table.selectedObjects.filter(isApplicable).forEach(perform)
+        }
+    }
+
+    init {
+        val actions = DefaultActionGroup(
+            AddAction(),
+            SelectionAction("Remove Port Forward", AllIcons.General.Remove, { true }) { manager.remove(it) },
+            Separator.getInstance(),
+            SelectionAction("Start Port Forward", AllIcons.Actions.Execute, { !it.isActive() }) { manager.start(it) },
+            SelectionAction("Stop Port Forward", AllIcons.Actions.Suspend, { it.isActive() }) { manager.stop(it) }
+        )
+        val toolbar = ActionManager
+            .getInstance()
+            .createActionToolbar("MirrordPortForwards", actions, true)
+        toolbar.targetComponent = this
+        setToolbar(toolbar.component)
+        setContent(ScrollPaneFactory.createScrollPane(table))
+
+        manager.addListener({ scheduleUpdate() }, parentDisposable)
+        update()
+    }
+
+    private fun MirrordPortForward.isActive(): Boolean {
+        return status == MirrordPortForward.Status.STARTING || status == MirrordPortForward.Status.RUNNING
+    }
+
+    private fun scheduleUpdate() {
+        if (updateAlarm.isDisposed) {
+            return
+        }
+
+        updateAlarm.cancelAllRequests()
+        updateAlarm.addRequest({ update() }, 100, ModalityState.any())
+    }
+
+    private fun update() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+
+        val forwards = manager.forwards
+        if (forwards == tableModel.items) {
+            // Same port forwards, only their status changed.
+            tableModel.fireTableDataChanged()
+            return
+        }
+
+        val selected = table.selectedObjects
+        tableModel.items = forwards
+        table.setSelection(selected.filter { forwards.contains(it) })
+    }
+}
+
+/**
+ * Asks for the local port, remote address and an optional target of a new port forward.
+ */
+private class AddPortForwardDialog(project: Project) : DialogWrapper(project) {
+    private val localPort = JBTextField()
+
+    private val remoteHost = JBTextField()
+
+    private val remotePort = JBTextField()
+
+    private val target = JBTextField().apply {
+        emptyText.text = "e.g. deployment/my-app, empty to use the target from the config or run targetless"
+    }
+
+    init {
+        title = "Add Port Forward"
+        init()
+    }
+
+    override fun createCenterPanel(): JComponent {
+        return FormBuilder
+            .createFormBuilder()
+            .addLabeledComponent("Local port:", localPort)
+            .addLabeledComponent("Remote host:", remoteHost)
+            .addLabeledComponent("Remote port:", remotePort)
+            .addLabeledComponent("Target:", target)
+            .panel
+    }
+
+    override fun getPreferredFocusedComponent(): JComponent = localPort
+
+    private fun parsePort(field: JBTextField): Int? = field.text.trim().toIntOrNull()?.takeIf { it in 1..65535 }
+
+    override fun doValidate(): ValidationInfo? {
+        if (parsePort(localPort) == null) {
+            return ValidationInfo("Enter a port between 1 and 65535", localPort)
+        }
+        if (remoteHost.text.isBlank() || remoteHost.text.contains(':')) {
+            return ValidationInfo("Enter a host name or an IPv4 address", remoteHost)
+        }
+        if (parsePort(remotePort) == null) {
+            return ValidationInfo("Enter a port between 1 and 65535", remotePort)
+        }
+
+        return null
+    }
+
+    fun portForward(): MirrordPortForward {
+        return MirrordPortForward(
+            parsePort(localPort)!!,
+            remoteHost.text.trim(),
+            parsePort(remotePort)!!,
+            target.text.trim().ifEmpty { null }
+        )
+    }
+}
@@ -1,13 +1,17 @@
 package com.metalbear.mirrord
 
+import com.intellij.execution.configurations.RunProfile
 import com.intellij.notification.Notification
 import com.intellij.notification.NotificationType
 import com.intellij.openapi.Disposable
 import com.intellij.openapi.application.ApplicationManager
 import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.service
 import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.Disposer
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.openapi.vfs.VirtualFileManager
+import java.util.concurrent.ConcurrentHashMap
 
 @Service(Service.Level.PROJECT)
 class MirrordProjectService(val project: Project) : Disposable {
@@ -23,8 +27,51 @@ class MirrordProjectService(val project: Project) : Disposable {
 
     val notifier: MirrordNotifier = MirrordNotifier(this)
 
+    val sessions: MirrordSessionManager = MirrordSessionManager()
+
+    val targetCache: MirrordTargetCache = MirrordTargetCache(this)
+
+    val kubeConfig: MirrordKubeConfigCache = MirrordKubeConfigCache(this)
+
+    val portForwards: MirrordPortForwardManager = MirrordPortForwardManager(this)
+
+    val operator: MirrordOperatorManager = MirrordOperatorManager(this)
+
+    val activeConfigs: MirrordActiveConfigState
+        get() = project.service<MirrordActiveConfigState>()
+
+    /**
+     * Config used by the run configurations that have no config bound, persisted in the workspace.
+     */
+    var activeConfig: VirtualFile?
+        get() = activeConfigs.activeConfig
+        set(value) {
+            activeConfigs.activeConfig = value
+        }
+
+    /**
+     * Kube context picked from the dropdown, overrides the one from the mirrord config and the kubeconfig.
+     * null if not overridden.
+     */
     @Volatile
-    var activeConfig: VirtualFile? = null
+    var kubeContext: String? = null
+
+    /**
+     * Extra environment for the next mirrord invocation of a run configuration, set by `IdeAction.RerunWithEnv`.
+     */
+    private val nextRunEnv: MutableMap<RunProfile, Map<String, String>> = ConcurrentHashMap()
+
+    fun overrideNextRunEnv(runProfile: RunProfile, env: Map<String, String>) {
+        nextRunEnv.merge(runProfile, env) { old, new -> old + new }
+    }
+
+    /**
+     * @param runProfile run configuration that started mirrord, null if it is not known
+     * @return extra environment for this mirrord invocation, cleared afterwards
+     */
+    fun takeNextRunEnv(runProfile: RunProfile?): Map<String, String> {
+        return runProfile?.let { nextRunEnv.remove(it) }.orEmpty()
+    }
 
     @Volatile
     private var _enabled = false
@@ -76,5 +123,6 @@ class MirrordProjectService(val project: Project) : Disposable {
 
     init {
         VirtualFileManager.getInstance().addAsyncFileListener(MirrordActiveConfigWatch(this), this)
+        Disposer.register(this, portForwards)
     }
 }
@@ -0,0 +1,98 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.openapi.fileChooser.FileChooserDescriptorFactory
+import com.intellij.openapi.options.SettingsEditor
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.ComboBox
+import com.intellij.openapi.ui.TextFieldWithBrowseButton
+import com.intellij.ui.EnumComboBoxModel
+import com.intellij.ui.components.JBCheckBox
+import com.intellij.ui.components.JBLabel
+import com.intellij.ui.components.JBTextField
+import com.intellij.util.ui.FormBuilder
+import javax.swing.JComponent
+import javax.swing.JPanel
+
+/**
+ * The "mirrord" tab of the run configuration editor, edits [MirrordRunConfigurationOptions].
+ *
+ * The type parameter is not bounded, because some product extensions (e.g. Node.js) work with run profiles
+ * that are not [RunConfigurationBase]. The options of such profiles are not edited.
+ */
+class MirrordRunConfigurationEditor<T>(project: Project?) : SettingsEditor<T>() {
+    companion object {
+        const val TITLE = "mirrord"
+    }
+
+    private val enableMode = ComboBox(EnumComboBoxModel(MirrordRunConfigurationOptions.EnableMode::class.java))
+
+    private val configFile = TextFieldWithBrowseButton().apply {
+        addBrowseFolderListener(
+            "mirrord Config File",
+            null,
+            project,
+            FileChooserDescriptorFactory
+                .createSingleFileDescriptor()
+                .withFileFilter { MirrordConfigAPI.isValidConfigExt(it) }
+        )
+        (textField as? JBTextField)?.emptyText?.text = "Use the active config or $CONFIG_ENV_NAME"
+    }
+
+    private val targetOverride = JBTextField().apply {
+        emptyText.text = "Use the config file or ask"
+        toolTipText = "For example pod/my-pod or deployment/my-deployment/container/my-container"
+    }
+
+    private val envPrecedence = ComboBox(EnumComboBoxModel(MirrordRunConfigurationOptions.EnvPrecedence::class.java))
+
+    private val rememberedTarget = JBLabel()
+
+    private val useRememberedTarget = JBCheckBox("Use the remembered target without asking")
+
+    /**
+     * Remembered target and namespace are not editable, they are preserved when the options are applied.
+     */
+    private var options = MirrordRunConfigurationOptions()
+
+    override fun resetEditorFrom(s: T) {
+        options = (s as? RunConfigurationBase<*>)?.let { MirrordRunConfigurationOptions.get(it) } ?: MirrordRunConfigurationOptions()
+
+        enableMode.selectedItem = options.enableMode
+        configFile.text = options.configFile.orEmpty()
+        targetOverride.text = options.targetOverride.orEmpty()
+        envPrecedence.selectedItem = options.envPrecedence
+        rememberedTarget.text = options.rememberedTarget ?: "none"
+        useRememberedTarget.isEnabled = options.rememberedTarget != null
+        useRememberedTarget.isSelected = options.targetMode == MirrordRunConfigurationOptions.TargetMode.REMEMBERED
+    }
+
+    override fun applyEditorTo(s: T) {
+        val configuration = s as? RunConfigurationBase<*> ?: return
+
+        options = options.copy(
+            enableMode = enableMode.selectedItem as? MirrordRunConfigurationOptions.EnableMode ?: MirrordRunConfigurationOptions.EnableMode.DEFAULT,
+            configFile = configFile.text.trim().ifEmpty { null },
+            targetOverride = targetOverride.text.trim().ifEmpty { null },
+            envPrecedence = envPrecedence.selectedItem as? MirrordRunConfigurationOptions.EnvPrecedence ?: MirrordRunConfigurationOptions.EnvPrecedence.REMOTE,
+            targetMode = if (useRememberedTarget.isSelected) {
+                MirrordRunConfigurationOptions.TargetMode.REMEMBERED
+            } else {
+                MirrordRunConfigurationOptions.TargetMode.ASK
+            }
+        )
+        MirrordRunConfigurationOptions.set(configuration, options)
+    }
+
+    override fun createEditor(): JComponent = FormBuilder
+        .createFormBuilder()
+        .addLabeledComponent("mirrord:", enableMode)
+        .addLabeledComponent("Config file:", configFile)
+        .addLabeledComponent("Target:", targetOverride)
+        .addLabeledComponent("Environment variables:", envPrecedence)
+        .addSeparator()
+        .addLabeledComponent("Remembered target:", rememberedTarget)
+        .addComponent(useRememberedTarget)
+        .addComponentFillVertically(JPanel(), 0)
+        .panel
+}
@@ -0,0 +1,29 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.CommonProgramRunConfigurationParameters
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.openapi.extensions.ExtensionPointName
+
+/**
+ * Provides the environment of run configurations that do not implement [CommonProgramRunConfigurationParameters],
+ * e.g. Bazel and Tomcat, which keep it in their own settings.
+ * Implemented by the product modules, next to the `ExecutionListener` that starts mirrord for the same configurations.
+ */
+interface MirrordRunConfigurationEnvProvider {
+    companion object {
+        val EP_NAME: ExtensionPointName<MirrordRunConfigurationEnvProvider> = ExtensionPointName.create("com.metalbear.mirrord.runConfigurationEnvProvider")
+
+        /**
+         * @return the environment set in the run configuration of [environment], null if it is not known
+         */
+        fun envFor(environment: ExecutionEnvironment): Map<String, String>? {
+            return EP_NAME.extensionList.firstNotNullOfOrNull { it.env(environment) }
+                ?: (environment.runProfile as? CommonProgramRunConfigurationParameters)?.envs
+        }
+    }
+
+    /**
+     * @return null if the run configuration of [environment] is not handled by this provider
+     */
+    fun env(environment: ExecutionEnvironment): Map<String, String>?
+}
@@ -0,0 +1,173 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.openapi.components.service
+import com.intellij.openapi.util.Key
+import org.jdom.Element
+
+/**
+ * mirrord options stored in a single run configuration, edited in the "mirrord" tab of the run configuration editor.
+ *
+ * @param enableMode whether mirrord is used when the run configuration is started
+ * @param configFile path to the mirrord config file, may contain IDE path macros and be relative to the working directory.
+ * Takes precedence over the active config, also set when a config is bound to the run configuration from the dropdown.
+ * null to use the active config, the `MIRRORD_CONFIG_FILE` environment variable or the default config
+ * @param targetOverride target to use instead of the one from the config file, the target selection dialog is not displayed
+ * @param envPrecedence which value is used when a variable is set both in the run configuration and in the remote environment
+ * @param rememberedTarget target the user chose for this run configuration, null if the user never chose one
+ * @param rememberedNamespace namespace override chosen together with [rememberedTarget]
+ * @param targetMode whether the target selection dialog is displayed for this run configuration
+ */
+data class MirrordRunConfigurationOptions(
+    val enableMode: EnableMode = EnableMode.DEFAULT,
+    val configFile: String? = null,
+    val targetOverride: String? = null,
+    val envPrecedence: EnvPrecedence = EnvPrecedence.REMOTE,
+    val rememberedTarget: String? = null,
+    val rememberedNamespace: String? = null,
+    val targetMode: TargetMode = TargetMode.ASK
+) {
+    enum class EnableMode(val presentableName: String) {
+        /**
+         * Follow the mirrord toggle in the toolbar, or the `MIRRORD_ACTIVE` environment variable.
+         */
+        DEFAULT("Follow the toolbar toggle"),
+        ENABLED("Always enabled"),
+        DISABLED("Always disabled");
+
+        override fun toString(): String = presentableName
+    }
+
+    enum class EnvPrecedence(val presentableName: String) {
+        /**
+         * Remote values override the values set in the run configuration.
+         */
+        REMOTE("Remote environment overrides local"),
+
+        /**
+         * Values set in the run configuration are kept.
+         */
+        LOCAL("Local environment overrides remote");
+
+        override fun toString(): String = presentableName
+    }
+
+    enum class TargetMode {
+        /**
+         * Display the target selection dialog on every run, with the remembered target preselected.
+         */
+        ASK,
+
+        /**
+         * Use the remembered target without displaying the dialog.
+         */
+        REMEMBERED
+    }
+
+    /**
+     * The remembered target, if it should be used without asking the user.
+     */
+    val targetToUse: String?
+        get() = rememberedTarget.takeIf { targetMode == TargetMode.REMEMBERED }
+
+    companion object {
+        private const val ELEMENT_NAME = "mirrord"
+        private const val ENABLE_MODE_ATTRIBUTE = "enableMode"
+        private const val CONFIG_FILE_ATTRIBUTE = "configFile"
+        private const val TARGET_OVERRIDE_ATTRIBUTE = "targetOverride"
+        private const val ENV_PRECEDENCE_ATTRIBUTE = "envPrecedence"
+        private const val REMEMBERED_TARGET_ATTRIBUTE = "rememberedTarget"
+        private const val REMEMBERED_NAMESPACE_ATTRIBUTE = "rememberedNamespace"
+        private const val TARGET_MODE_ATTRIBUTE = "targetMode"
+
+        /**
+         * Copyable, so that the options are preserved when the user copies the run configuration.
+         */
+        private val KEY: Key<MirrordRunConfigurationOptions> = Key.create("mirrord.runConfigurationOptions")
+
+        /**
+         * Set on run configurations whose options are serialized by a run configuration extension.
+         * The options of the other run configurations are stored in [MirrordRunConfigurationOptionsState].
+         */
+        private val PERSISTED_BY_EXTENSION: Key<Boolean> = Key.create("mirrord.runConfigurationOptionsPersisted")
+
+        fun get(configuration: RunConfigurationBase<*>): MirrordRunConfigurationOptions {
+            return configuration.getCopyableUserData(KEY)
+                ?: configuration.project.service<MirrordRunConfigurationOptionsState>().restore(configuration)
+                ?: MirrordRunConfigurationOptions()
+        }
+
+        fun set(configuration: RunConfigurationBase<*>, options: MirrordRunConfigurationOptions) {
+            configuration.putCopyableUserData(KEY, options)
+            // Options set from the dropdown or the target dialog do not go through the run configuration editor.
+            configuration.project.service<MirrordConfigReferences>().invalidate()
+        }
+
+        fun isPersistedByExtension(configuration: RunConfigurationBase<*>): Boolean {
+            return configuration.getUserData(PERSISTED_BY_EXTENSION) == true
+        }
+
+        /**
+         * Reads the options from the serialized run configuration, called by the run configuration extensions.
+         */
+        fun readExternal(configuration: RunConfigurationBase<*>, element: Element) {
+            configuration.putUserData(PERSISTED_BY_EXTENSION, true)
+            set(configuration, read(element))
+        }
+
+        /**
+         * Writes the options to the serialized run configuration, called by the run configuration extensions.
+         */
+        fun writeExternal(configuration: RunConfigurationBase<*>, element: Element) {
+            configuration.putUserData(PERSISTED_BY_EXTENSION, true)
+            write(get(configuration), element)
+        }
+
+        /**
+         * @return the options from the child of [element], the default ones if there is no child
+         */
+        fun read(element: Element): MirrordRunConfigurationOptions {
+            val child = element.getChild(ELEMENT_NAME) ?: return MirrordRunConfigurationOptions()
+
+            return MirrordRunConfigurationOptions(
+                enumAttribute(child, ENABLE_MODE_ATTRIBUTE, EnableMode.DEFAULT),
+                child.getAttributeValue(CONFIG_FILE_ATTRIBUTE),
+                child.getAttributeValue(TARGET_OVERRIDE_ATTRIBUTE),
+                enumAttribute(child, ENV_PRECEDENCE_ATTRIBUTE, EnvPrecedence.REMOTE),
+                child.getAttributeValue(REMEMBERED_TARGET_ATTRIBUTE),
+                child.getAttributeValue(REMEMBERED_NAMESPACE_ATTRIBUTE),
+                enumAttribute(child, TARGET_MODE_ATTRIBUTE, TargetMode.ASK)
+            )
+        }
+
+        /**
+         * Writes the options as a child of [element]. Nothing is written if the options are the default ones.
+         *
+         * @return false if nothing was written
+         */
+        fun write(options: MirrordRunConfigurationOptions, element: Element): Boolean {
+            if (options == MirrordRunConfigurationOptions()) {
+                return false
+            }
+
+            val child = Element(ELEMENT_NAME)
+            child.setAttribute(ENABLE_MODE_ATTRIBUTE, options.enableMode.name)
+            options.configFile?.let { child.setAttribute(CONFIG_FILE_ATTRIBUTE, it) }
+            options.targetOverride?.let { child.setAttribute(TARGET_OVERRIDE_ATTRIBUTE, it) }
+            child.setAttribute(ENV_PRECEDENCE_ATTRIBUTE, options.envPrecedence.name)
+            options.rememberedTarget?.let { child.setAttribute(REMEMBERED_TARGET_ATTRIBUTE, it) }
+            options.rememberedNamespace?.let { child.setAttribute(REMEMBERED_NAMESPACE_ATTRIBUTE, it) }
+            child.setAttribute(TARGET_MODE_ATTRIBUTE, options.targetMode.name)
+            element.addContent(child)
+            return true
+        }
+
+        /**
+         * Unknown values (e.g. written by a newer version of the plugin) are replaced with the default.
+         */
+        private inline fun <reified T : Enum<T>> enumAttribute(element: Element, name: String, default: T): T {
+            val value = element.getAttributeValue(name) ?: return default
+            return enumValues<T>().find { it.name == value } ?: default
+        }
+    }
+}
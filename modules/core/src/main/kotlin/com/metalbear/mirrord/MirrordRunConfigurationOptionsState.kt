@@ -0,0 +1,84 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.RunManager
+import com.intellij.execution.RunManagerListener
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.openapi.components.PersistentStateComponent
+import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.State
+import com.intellij.openapi.components.Storage
+import com.intellij.openapi.components.StoragePathMacros
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.Project
+import org.jdom.Element
+import java.util.concurrent.ConcurrentHashMap
+
+/**
+ * [MirrordRunConfigurationOptions] of the run configurations that have no run configuration extension to serialize them,
+ * e.g. Bazel and Rider. These have no mirrord tab either: the Bazel plugin builds its editor from the handler state
+ * without an extension point for extra tabs, and the editors of Rider run configurations are provided by its backend.
+ * Their options are set outside of an editor, e.g. by the target dialog.
+ *
+ * The options are kept in the run configuration like for the others, and are written here only when the workspace is saved.
+ * This way a renamed run configuration keeps its options, and the options of a removed one are dropped.
+ */
+@Service(Service.Level.PROJECT)
+@State(name = "MirrordRunConfigurationOptions", storages = [Storage(StoragePathMacros.WORKSPACE_FILE)])
+class MirrordRunConfigurationOptionsState(private val project: Project) : PersistentStateComponent<Element> {
+    companion object {
+        private const val CONFIGURATION_ELEMENT = "configuration"
+        private const val KEY_ATTRIBUTE = "key"
+    }
+
+    /**
+     * Restores the options as soon as the run configurations are loaded, before they can be renamed.
+     */
+    class Loader(private val project: Project) : RunManagerListener {
+        override fun stateLoaded(runManager: RunManager, isFirstLoadState: Boolean) {
+            project.service<MirrordRunConfigurationOptionsState>().restoreAll(runManager)
+        }
+    }
+
+    /**
+     * Options read from the workspace, by [key]. Moved to the run configuration when it's first used.
+     */
+    private val loaded = ConcurrentHashMap<String, MirrordRunConfigurationOptions>()
+
+    override fun getState(): Element {
+        val state = Element("state")
+        RunManager
+            .getInstance(project)
+            .allConfigurationsList
+            .filterIsInstance<RunConfigurationBase<*>>()
+            .filterNot { MirrordRunConfigurationOptions.isPersistedByExtension(it) }
+            .forEach { configuration ->
+                val element = Element(CONFIGURATION_ELEMENT).setAttribute(KEY_ATTRIBUTE, key(configuration))
+                if (MirrordRunConfigurationOptions.write(MirrordRunConfigurationOptions.get(configuration), element)) {
+                    state.addContent(element)
+                }
+            }
+        return state
+    }
+
+    override fun loadState(state: Element) {
+        loaded.clear()
+        state.getChildren(CONFIGURATION_ELEMENT).forEach { element ->
+            element.getAttributeValue(KEY_ATTRIBUTE)?.let { loaded[it] = MirrordRunConfigurationOptions.read(element) }
+        }
+    }
+
+    /**
+     * Moves the stored options to the run configuration.
+     *
+     * @return null if there are no stored options for the run configuration
+     */
+    fun restore(configuration: RunConfigurationBase<*>): MirrordRunConfigurationOptions? {
+        return loaded.remove(key(configuration))?.also { MirrordRunConfigurationOptions.set(configuration, it) }
+    }
+
+    private fun restoreAll(runManager: RunManager) {
+        runManager.allConfigurationsList.filterIsInstance<RunConfigurationBase<*>>().forEach { MirrordRunConfigurationOptions.get(it) }
+    }
+
+    private fun key(configuration: RunConfigurationBase<*>): String = "${configuration.type.id}.${configuration.name}"
+}
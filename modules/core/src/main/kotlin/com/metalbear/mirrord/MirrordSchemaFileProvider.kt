@@ -1,15 +1,34 @@
 package com.metalbear.mirrord
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.Project
 import com.intellij.openapi.vfs.VirtualFile
 import com.jetbrains.jsonSchema.extension.JsonSchemaFileProvider
 import com.jetbrains.jsonSchema.extension.SchemaType
 import com.jetbrains.jsonSchema.impl.JsonSchemaVersion
 import com.jetbrains.jsonSchema.remote.JsonFileResolver
 
-class MirrordSchemaFileProvider : JsonSchemaFileProvider {
+/**
+ * Provides the mirrord config schema for completion and validation.
+ *
+ * The schema service is shared by all languages: YAML configs get it through the JSON schema support of the YAML plugin,
+ * and TOML configs through the one of the TOML plugin, enabled for mirrord configs by [MirrordTomlSchemaEnabler].
+ * Both need the same key paths as JSON, which the mirrord config keeps across formats.
+ * The schema is applied to files with `mirrord` in the path, as before, and to the other configs of the project,
+ * see [MirrordConfigAPI.isProjectConfig].
+ *
+ * The schema matches the version of the mirrord binary in use, see [MirrordSchemaManager].
+ * Until a schema was cached, the one from the latest release is used.
+ */
+class MirrordSchemaFileProvider(private val project: Project) : JsonSchemaFileProvider {
+    companion object {
+        private const val LATEST_SCHEMA_URL = "https://raw.githubusercontent.com/metalbear-co/mirrord/latest/mirrord-schema.json"
+    }
 
     override fun isAvailable(file: VirtualFile): Boolean {
-        val path = file.path
-        return MirrordConfigAPI.isConfigFilePath(file) && path.endsWith(".json")
+        if (MirrordConfigAPI.isConfigFilePath(file)) {
+            return true
+        }
+        return !project.isDisposed && project.service<MirrordProjectService>().configApi.isProjectConfig(file)
     }
 
     override fun getName(): String {
@@ -17,15 +36,15 @@ class MirrordSchemaFileProvider : JsonSchemaFileProvider {
     }
 
     override fun getSchemaFile(): VirtualFile? {
-        return JsonFileResolver.urlToFile(this.remoteSource)
+        return service<MirrordSchemaManager>().schemaFile() ?: JsonFileResolver.urlToFile(LATEST_SCHEMA_URL)
     }
 
     override fun getSchemaType(): SchemaType {
-        return SchemaType.remoteSchema
+        return if (service<MirrordSchemaManager>().schemaFile() != null) SchemaType.schema else SchemaType.remoteSchema
     }
 
-    override fun getRemoteSource(): String {
-        return "https://raw.githubusercontent.com/metalbear-co/mirrord/latest/mirrord-schema.json"
+    override fun getRemoteSource(): String? {
+        return if (service<MirrordSchemaManager>().schemaFile() != null) null else LATEST_SCHEMA_URL
     }
 
     override fun getSchemaVersion(): JsonSchemaVersion {
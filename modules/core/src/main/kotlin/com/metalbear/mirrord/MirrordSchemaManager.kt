@@ -0,0 +1,172 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.ProjectManager
+import com.intellij.openapi.vfs.LocalFileSystem
+import com.intellij.openapi.vfs.VirtualFile
+import com.intellij.util.text.VersionComparatorUtil
+import com.jetbrains.jsonSchema.ide.JsonSchemaService
+import java.net.URI
+import java.net.http.HttpClient
+import java.net.http.HttpRequest
+import java.net.http.HttpResponse
+import java.nio.file.Files
+import java.nio.file.Path
+import java.nio.file.StandardCopyOption
+import java.time.Duration
+import java.util.*
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.atomic.AtomicBoolean
+import kotlin.io.path.isDirectory
+import kotlin.io.path.name
+
+private const val SCHEMA_FILE = "mirrord-schema.json"
+private const val SCHEMA_ENDPOINT = "https://raw.githubusercontent.com/metalbear-co/mirrord"
+
+/**
+ * Keeps a local copy of the config schema matching the version of the mirrord binary in use.
+ * Schemas are cached per version in the plugin directory, so that they are available offline.
+ * When the binary changes, the schema of the new version is fetched and the schema service is reset.
+ */
+@Service(Service.Level.APP)
+class MirrordSchemaManager : Disposable {
+    /**
+     * Cached schema currently provided to the editors, null if there is none.
+     */
+    @Volatile
+    private var schema: VirtualFile? = null
+
+    /**
+     * Version of the binary that [schema] was last resolved for, set only once its schema is cached,
+     * so that a failed download is retried the next time the binary is used.
+     */
+    @Volatile
+    private var resolvedVersion: String? = null
+
+    /**
+     * Versions that are being resolved at the moment.
+     */
+    private val resolving: MutableSet<String> = ConcurrentHashMap.newKeySet()
+
+    /**
+     * Set once the version of the local binary was looked up, before any binary was used.
+     */
+    private val lookedUp = AtomicBoolean(false)
+
+    init {
+        service<MirrordBinaryManager>().addListener({ version -> resolve(version) }, this)
+    }
+
+    /**
+     * @return the cached schema for the binary in use, the newest cached schema if there is none for this version,
+     *         null if nothing was cached yet
+     */
+    fun schemaFile(): VirtualFile? {
+        val version = service<MirrordBinaryManager>().usedVersion
+        if (version == null && lookedUp.compareAndSet(false, true)) {
+            ApplicationManager.getApplication().executeOnPooledThread {
+                val localVersion = service<MirrordBinaryManager>().findLocalBinaryVersion(null)
+                if (localVersion != null) {
+                    resolve(localVersion)
+                } else {
+                    update(newestCached())
+                }
+            }
+        }
+
+        return schema?.takeIf { it.isValid }
+    }
+
+    /**
+     * Makes the schema of the given version the provided one, downloading it if it's not cached.
+     * If the download fails, the newest cached schema not above this version is used instead.
+     */
+    private fun resolve(version: String) {
+        if (resolvedVersion == version || !resolving.add(version)) {
+            return
+        }
+
+        ApplicationManager.getApplication().executeOnPooledThread {
+            try {
+                val path = try {
+                    (cachedPath(version).takeIf { Files.exists(it) } ?: download(version)).also { resolvedVersion = version }
+                } catch (e: Exception) {
+                    MirrordLogger.logger.debug("failed to fetch the config schema for mirrord $version", e)
+                    newestCached(version)
+                }
+                update(path)
+            } finally {
+                resolving.remove(version)
+            }
+        }
+    }
+
+    private fun update(path: Path?) {
+        val file = path?.let { LocalFileSystem.getInstance().refreshAndFindFileByNioFile(it) }
+        if (file == null || file == schema) {
+            return
+        }
+
+        schema = file
+        ApplicationManager.getApplication().invokeLater {
+            ProjectManager.getInstance().openProjects
+                .filterNot { it.isDisposed }
+                .forEach { JsonSchemaService.Impl.get(it).reset() }
+        }
+    }
+
+    private fun cachedPath(version: String): Path = MirrordPathManager.getSchemaDir().resolve(version).resolve(SCHEMA_FILE)
+
+    /**
+     * A newer schema may describe options the binary does not support, so versions above [maxVersion] are skipped.
+     *
+     * @param maxVersion version of the binary in use, null if it is not known
+     * @return the cached schema of the highest version, null if nothing was cached yet
+     */
+    private fun newestCached(maxVersion: String? = null): Path? {
+        val dir = MirrordPathManager.getSchemaDir()
+        if (!dir.isDirectory()) {
+            return null
+        }
+
+        return Files.list(dir).use { versions ->
+            versions
+                .filter { Files.exists(it.resolve(SCHEMA_FILE)) }
+                .filter { maxVersion == null || VersionComparatorUtil.compare(it.name, maxVersion) <= 0 }
+                .max { a, b -> VersionComparatorUtil.compare(a.name, b.name) }
+                .map { it.resolve(SCHEMA_FILE) }
+                .orElse(null)
+        }
+    }
+
+    /**
+     * Fetches the schema from the release tag of the given version.
+     */
+    private fun download(version: String): Path {
+        val client = HttpClient.newHttpClient()
+        val request = HttpRequest
+            .newBuilder(URI("$SCHEMA_ENDPOINT/$version/$SCHEMA_FILE"))
+            .timeout(Duration.ofSeconds(10L))
+            .GET()
+            .build()
+
+        val response = client.send(request, HttpResponse.BodyHandlers.ofByteArray())
+        if (response.statusCode() != 200) {
+            throw RuntimeException("unexpected status code ${response.statusCode()}")
+        }
+
+        val destination = cachedPath(version)
+        Files.createDirectories(destination.parent)
+
+        val tmpDestination = destination.resolveSibling(destination.name + UUID.randomUUID().toString())
+        Files.write(tmpDestination, response.body())
+        Files.move(tmpDestination, destination, StandardCopyOption.REPLACE_EXISTING)
+
+        return destination
+    }
+
+    override fun dispose() {}
+}
@@ -0,0 +1,207 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.openapi.Disposable
+import com.intellij.util.EventDispatcher
+import java.time.Duration
+import java.time.Instant
+import java.util.EventListener
+import java.util.concurrent.CopyOnWriteArrayList
+import java.util.concurrent.atomic.AtomicInteger
+
+/**
+ * How many sessions are kept in memory (and displayed in the tool window).
+ */
+private const val MAX_SESSIONS = 30
+
+/**
+ * A single task reported by the mirrord binary with the `NewTask` and `FinishedTask` progress messages.
+ *
+ * @param parent null if this is a top level task of the session.
+ */
+class MirrordSessionTask(val name: String, val parent: MirrordSessionTask?, val startedAt: Instant) {
+    val children: MutableList<MirrordSessionTask> = CopyOnWriteArrayList()
+
+    @Volatile
+    var finishedAt: Instant? = null
+        private set
+
+    /**
+     * null if the task has not finished yet.
+     */
+    @Volatile
+    var success: Boolean? = null
+        private set
+
+    /**
+     * Optional message that came with the `FinishedTask` progress message.
+     */
+    @Volatile
+    var message: String? = null
+        private set
+
+    val duration: Duration?
+        get() = finishedAt?.let { Duration.between(startedAt, it) }
+
+    fun finish(success: Boolean, message: String?) {
+        this.finishedAt = Instant.now()
+        this.success = success
+        this.message = message
+    }
+}
+
+/**
+ * A single `mirrord ext` invocation, with all the tasks reported by the mirrord binary.
+ *
+ * @param target target passed to the mirrord binary, null if the target is taken from the config or mirrord runs targetless.
+ * @param run the run that started mirrord, null if it is not known
+ */
+class MirrordSession(private val manager: MirrordSessionManager, val id: Int, val target: String?, val run: ExecutionEnvironment?) {
+    val startedAt: Instant = Instant.now()
+
+    /**
+     * Top level tasks of this session.
+     */
+    val tasks: MutableList<MirrordSessionTask> = CopyOnWriteArrayList()
+
+    /**
+     * All tasks of this session, in the order they were started.
+     */
+    private val allTasks: MutableList<MirrordSessionTask> = CopyOnWriteArrayList()
+
+    @Volatile
+    var finishedAt: Instant? = null
+        private set
+
+    /**
+     * Changes mirrord made to the environment of the process, null if mirrord did not start yet.
+     */
+    @Volatile
+    var environment: MirrordEnvironmentDiff? = null
+        private set
+
+    /**
+     * Result of `mirrord ext` as it was applied to the process, null if mirrord did not start yet.
+     */
+    @Volatile
+    var execution: MirrordExecution? = null
+        private set
+
+    /**
+     * null if mirrord is still starting.
+     */
+    @Volatile
+    var success: Boolean? = null
+        private set
+
+    /**
+     * Finds the latest started task with the given name that has not finished yet.
+     */
+    private fun findRunning(name: String): MirrordSessionTask? {
+        return allTasks.lastOrNull { it.name == name && it.finishedAt == null }
+    }
+
+    /**
+     * Handles the `NewTask` progress message.
+     */
+    fun startTask(name: String, parentName: String?) {
+        val parent = parentName?.let { findRunning(it) }
+        val task = MirrordSessionTask(name, parent, Instant.now())
+
+        allTasks.add(task)
+        (parent?.children ?: tasks).add(task)
+
+        manager.fireChanged()
+    }
+
+    /**
+     * Handles the `FinishedTask` progress message.
+     */
+    fun finishTask(name: String, success: Boolean, message: String?) {
+        val task = findRunning(name) ?: run {
+            MirrordLogger.logger.debug("received `FinishedTask` for an unknown task $name")
+            return
+        }
+
+        task.finish(success, message)
+        manager.fireChanged()
+    }
+
+    /**
+     * @param localEnv environment of the process without mirrord, see [MirrordEnvironmentDiff.compute]
+     */
+    fun recordExecution(execution: MirrordExecution, localEnv: Map<String, String>?) {
+        this.execution = execution
+        this.environment = MirrordEnvironmentDiff.compute(localEnv, execution)
+        manager.fireChanged()
+    }
+
+    /**
+     * Marks the whole session as finished.
+     * When the session failed, tasks that are still running are marked as failed as well.
+     */
+    fun finish(success: Boolean) {
+        if (!success) {
+            allTasks.filter { it.finishedAt == null }.forEach { it.finish(false, null) }
+        }
+
+        this.finishedAt = Instant.now()
+        this.success = success
+
+        manager.fireChanged()
+    }
+}
+
+/**
+ * Keeps track of the latest mirrord sessions in the project.
+ * Displayed in the mirrord tool window.
+ */
+class MirrordSessionManager {
+    fun interface Listener : EventListener {
+        /**
+         * Called from an arbitrary thread whenever a session is started, updated or removed.
+         */
+        fun sessionsChanged()
+    }
+
+    private val dispatcher = EventDispatcher.create(Listener::class.java)
+
+    private val nextId = AtomicInteger(1)
+
+    private val _sessions: MutableList<MirrordSession> = CopyOnWriteArrayList()
+
+    /**
+     * Sessions, the latest first.
+     */
+    val sessions: List<MirrordSession>
+        get() = _sessions.reversed()
+
+    fun startSession(target: String?, run: ExecutionEnvironment?): MirrordSession {
+        val session = MirrordSession(this, nextId.getAndIncrement(), target, run)
+
+        _sessions.add(session)
+        while (_sessions.size > MAX_SESSIONS) {
+            _sessions.removeAt(0)
+        }
+
+        fireChanged()
+
+        return session
+    }
+
+    /**
+     * Removes all sessions that are not starting at the moment.
+     */
+    fun clearFinished() {
+        _sessions.removeIf { it.finishedAt != null }
+        fireChanged()
+    }
+
+    fun addListener(listener: Listener, parentDisposable: Disposable) {
+        dispatcher.addListener(listener, parentDisposable)
+    }
+
+    internal fun fireChanged() {
+        dispatcher.multicaster.sessionsChanged()
+    }
+}
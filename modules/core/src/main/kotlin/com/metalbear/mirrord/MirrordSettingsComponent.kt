@@ -1,5 +1,6 @@
 package com.metalbear.mirrord
 
+import com.intellij.ui.JBIntSpinner
 import com.intellij.ui.components.JBCheckBox
 import com.intellij.ui.components.JBLabel
 import com.intellij.ui.components.JBTextField
@@ -32,6 +33,10 @@ class MirrordSettingsComponent {
             }
         }
 
+    private val targetsRefreshInterval = JBIntSpinner(60, 0, 3600, 10).apply {
+        toolTipText = "0 disables the background refresh"
+    }
+
     private val autoUpdatePanel = FormBuilder
         .createFormBuilder()
         .addComponent(autoUpdate)
@@ -47,6 +52,8 @@ class MirrordSettingsComponent {
         .addSeparator()
         .addComponent(autoUpdatePanel)
         .addSeparator()
+        .addLabeledComponent("Refresh cached targets every (seconds):", targetsRefreshInterval)
+        .addSeparator()
         .addComponent(JBLabel("Notify when:"))
         .apply {
             notificationsEnabled.forEach {
@@ -90,6 +97,12 @@ class MirrordSettingsComponent {
             autoUpdate.isSelected = value
         }
 
+    var targetsRefreshIntervalStatus: Int
+        get() = targetsRefreshInterval.number
+        set(value) {
+            targetsRefreshInterval.number = value
+        }
+
     var mirrordVersionStatus: String
         get() = mirrordVersion.text
         set(value) {
@@ -20,7 +20,8 @@ class MirrordSettingsConfigurable : Configurable {
                 (usageBannerEnabledStatus != settings.showUsageBanner) ||
                 (autoUpdateEnabledStatus != settings.autoUpdate) ||
                 (mirrordVersionStatus != settings.mirrordVersion) ||
-                (enabledOnStartupStatus != settings.enabledByDefault)
+                (enabledOnStartupStatus != settings.enabledByDefault) ||
+                (targetsRefreshIntervalStatus != settings.targetsRefreshInterval)
         }
     }
 
@@ -33,6 +34,7 @@ class MirrordSettingsConfigurable : Configurable {
             settings.autoUpdate = autoUpdateEnabledStatus
             settings.mirrordVersion = mirrordVersionStatus
             settings.enabledByDefault = enabledOnStartupStatus
+            settings.targetsRefreshInterval = targetsRefreshIntervalStatus
         }
     }
 
@@ -45,6 +47,7 @@ class MirrordSettingsConfigurable : Configurable {
             notificationsDisabledStatus = settings.disabledNotifications.orEmpty()
             usageBannerEnabledStatus = settings.showUsageBanner
             enabledOnStartupStatus = settings.enabledByDefault
+            targetsRefreshIntervalStatus = settings.targetsRefreshInterval
         }
     }
 
@@ -22,6 +22,7 @@ open class MirrordSettingsState : PersistentStateComponent<MirrordSettingsState.
     // after automatically loading our save state,  we will keep reference to it
     override fun loadState(state: MirrordState) {
         mirrordState = state
+        state.migrateKindFilters()
     }
 
     enum class NotificationId(val presentableName: String) {
@@ -34,23 +35,51 @@ open class MirrordSettingsState : PersistentStateComponent<MirrordSettingsState.
         AGENT_VERSION_MISMATCH("agent version does not match version of the local mirrord installation"),
         PLUGIN_REVIEW("mirrord occasionally asks for plugin review"),
         DISCORD_INVITE("mirrord offers a Discord server invitation"),
-        MIRRORD_FOR_TEAMS("mirrord occasionally informs about mirrord for Teams")
+        MIRRORD_FOR_TEAMS("mirrord occasionally informs about mirrord for Teams"),
+        OPERATOR_LICENSE_EXPIRY("mirrord operator license is about to expire"),
+        EDITOR_VERIFICATION_UNAVAILABLE("mirrord config is not verified in the editor on Windows")
     }
 
     class MirrordState {
         var versionCheckEnabled: Boolean? = null
         var autoUpdate: Boolean = true
         var mirrordVersion: String = ""
-        var lastChosenTarget: String? = null
+
+        /**
+         * Visibility of target kinds in the target selection dialog, kinds that are not in the map are visible.
+         */
+        var targetKindsVisibility: MutableMap<String, Boolean> = HashMap()
+
+        /**
+         * Replaced with [targetKindsVisibility], kept only to migrate the old settings.
+         */
         var showPodsInSelection: Boolean? = null
         var showDeploymentsInSelection: Boolean? = null
         var showRolloutsInSelection: Boolean? = null
+
         var disabledNotifications: Set<NotificationId>? = null
         var showUsageBanner: Boolean = true
         var runsCounter: Int = 0
         var operatorUsed: Boolean = false
         var enabledByDefault: Boolean = false
 
+        /**
+         * How often (in seconds) cached target listings are refreshed in the background, 0 disables the refresh.
+         */
+        var targetsRefreshInterval: Int = 60
+
+        /**
+         * Moves the old per-kind settings to [targetKindsVisibility].
+         */
+        fun migrateKindFilters() {
+            showPodsInSelection?.let { targetKindsVisibility.putIfAbsent("pod", it) }
+            showDeploymentsInSelection?.let { targetKindsVisibility.putIfAbsent("deployment", it) }
+            showRolloutsInSelection?.let { targetKindsVisibility.putIfAbsent("rollout", it) }
+            showPodsInSelection = null
+            showDeploymentsInSelection = null
+            showRolloutsInSelection = null
+        }
+
         fun disableNotification(id: NotificationId) {
             disabledNotifications = disabledNotifications.orEmpty() + id
         }
@@ -0,0 +1,48 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.ExecutionListener
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.execution.configurations.RunProfile
+import com.intellij.execution.process.ProcessHandler
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.openapi.components.Service
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.Project
+import java.util.concurrent.ConcurrentLinkedDeque
+
+/**
+ * Runs that are being started. mirrord is started while the command line of the run is patched,
+ * and some products give only the command line, so this is used to find the run the command line belongs to.
+ */
+@Service(Service.Level.PROJECT)
+class MirrordStartingRuns {
+    /**
+     * Tracks the runs between the start of the execution and the start of the process,
+     * which is when the command line is patched.
+     */
+    class Listener(private val project: Project) : ExecutionListener {
+        override fun processStarting(executorId: String, env: ExecutionEnvironment) {
+            project.service<MirrordStartingRuns>().starting.addLast(env)
+        }
+
+        override fun processStarted(executorId: String, env: ExecutionEnvironment, handler: ProcessHandler) {
+            project.service<MirrordStartingRuns>().starting.remove(env)
+        }
+
+        override fun processNotStarted(executorId: String, env: ExecutionEnvironment) {
+            project.service<MirrordStartingRuns>().starting.remove(env)
+        }
+    }
+
+    private val starting = ConcurrentLinkedDeque<ExecutionEnvironment>()
+
+    /**
+     * @return the run configuration that started last and has no process yet, null if there is none
+     */
+    fun current(): RunConfigurationBase<*>? = starting.peekLast()?.runProfile as? RunConfigurationBase<*>
+
+    /**
+     * @return the latest starting run of the run configuration, null if it is not being started
+     */
+    fun find(runProfile: RunProfile): ExecutionEnvironment? = starting.lastOrNull { it.runProfile === runProfile }
+}
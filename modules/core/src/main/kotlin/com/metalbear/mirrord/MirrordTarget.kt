@@ -0,0 +1,101 @@
+package com.metalbear.mirrord
+
+import com.google.gson.annotations.SerializedName
+
+/**
+ * A single entry of the rich `mirrord ls` output.
+ *
+ * @param path for example `pod/my-pod` or `pod/my-pod/container/my-container`.
+ * @param available whether the target can be used at the moment (e.g. the pod is running).
+ */
+data class FoundTarget(val path: String, val available: Boolean?)
+
+/**
+ * Rich `mirrord ls` output, returned when `MIRRORD_LS_RICH_OUTPUT` is set.
+ *
+ * Older versions of the mirrord binary ignore the variable and output a plain list of paths.
+ */
+data class FoundTargets(
+    val targets: List<FoundTarget>?,
+    @SerializedName("current_namespace") val currentNamespace: String?,
+    val namespaces: List<String>?
+)
+
+/**
+ * A mirrord target (pod, deployment, rollout, ...) found in the cluster.
+ *
+ * @param kind for example `pod`, `deployment`, `rollout`.
+ * @param namespace null if not known (older versions of the mirrord binary don't report it).
+ * @param containers names of the target's containers. The mirrord binary reports containers only for targets that
+ * have more than one, so this is empty for single-container targets.
+ */
+data class MirrordTarget(
+    val kind: String,
+    val name: String,
+    val namespace: String?,
+    val containers: List<String>,
+    val available: Boolean
+) {
+    /**
+     * Path of the target that can be passed to the mirrord binary, without the container.
+     */
+    val path: String
+        get() = "$kind/$name"
+
+    /**
+     * @return path of the target that can be passed to the mirrord binary, with the given container.
+     */
+    fun pathWithContainer(container: String?): String {
+        return container?.let { "$path/container/$it" } ?: path
+    }
+
+    /**
+     * @return whether the given path points to this target (possibly to one of its containers).
+     */
+    fun matches(targetPath: String): Boolean {
+        return targetPath == path || targetPath.startsWith("$path/container/")
+    }
+
+    companion object {
+        /**
+         * Splits a target path (`kind/name[/container/container-name]`) into its parts.
+         *
+         * @return kind, name and container (null if not present), or null if the path is invalid.
+         */
+        fun parsePath(path: String): Triple<String, String, String?>? {
+            val parts = path.split('/')
+            if (parts.size < 2 || parts[0].isEmpty() || parts[1].isEmpty()) {
+                return null
+            }
+
+            val container = if (parts.size >= 4 && parts[2] == "container") parts[3] else null
+            return Triple(parts[0], parts[1], container)
+        }
+
+        /**
+         * Groups found targets that differ only in the container.
+         */
+        fun fromFound(found: List<FoundTarget>, namespace: String?): List<MirrordTarget> {
+            return found
+                .mapNotNull { target -> parsePath(target.path)?.let { Pair(it, target.available ?: true) } }
+                .groupBy { (parsed, _) -> Pair(parsed.first, parsed.second) }
+                .map { (kindAndName, entries) ->
+                    MirrordTarget(
+                        kindAndName.first,
+                        kindAndName.second,
+                        namespace,
+                        entries.mapNotNull { (parsed, _) -> parsed.third },
+                        entries.any { (_, available) -> available }
+                    )
+                }
+        }
+    }
+}
+
+/**
+ * Result of `mirrord ls`.
+ *
+ * @param currentNamespace namespace that was listed, null if not known.
+ * @param namespaces all namespaces the user can list, empty if not known.
+ */
+data class MirrordTargetListing(val targets: List<MirrordTarget>, val currentNamespace: String?, val namespaces: List<String>)
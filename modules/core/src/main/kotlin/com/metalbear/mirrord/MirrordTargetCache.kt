@@ -0,0 +1,196 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.progress.EmptyProgressIndicator
+import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.progress.ProgressIndicator
+import com.intellij.openapi.util.Disposer
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.openapi.vfs.AsyncFileListener
+import com.intellij.openapi.vfs.VirtualFileManager
+import com.intellij.openapi.vfs.newvfs.events.VFileEvent
+import com.intellij.util.concurrency.AppExecutorUtil
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.ScheduledFuture
+import java.util.concurrent.TimeUnit
+import java.util.concurrent.atomic.AtomicBoolean
+
+/**
+ * How often the cache checks whether some listings should be refreshed.
+ */
+private const val REFRESH_CHECK_INTERVAL_SECONDS = 10L
+
+/**
+ * Listings that were not used for this long are dropped instead of being refreshed.
+ */
+private val UNUSED_ENTRY_TTL_MILLIS = TimeUnit.MINUTES.toMillis(30)
+
+/**
+ * Background refresh of a single listing is cancelled after this time.
+ */
+private const val REFRESH_TIMEOUT_MINUTES = 2L
+
+/**
+ * Identifies a `mirrord ls` result.
+ *
+ * @param kubeContext context used to list the targets, null if not known
+ * @param namespace namespace override, null if the namespace was taken from the config
+ * @param configFile path to the config file used to list the targets, null if there was no config file
+ * @param env environment of the run configuration passed to the mirrord binary, it can change the cluster or the credentials
+ */
+data class TargetListingKey(val kubeContext: String?, val namespace: String?, val configFile: String?, val env: Map<String, String>?) {
+    // The environment may contain secrets, it's left out of the logs.
+    override fun toString(): String = "TargetListingKey(kubeContext=$kubeContext, namespace=$namespace, configFile=$configFile)"
+}
+
+/**
+ * Caches `mirrord ls` results, so that the target selection dialog can display the targets instantly.
+ *
+ * The cached listings are refreshed in the background every [MirrordSettingsState.MirrordState.targetsRefreshInterval]
+ * seconds, and invalidated when the config file they were listed with changes.
+ */
+class MirrordTargetCache(private val service: MirrordProjectService) : Disposable {
+    private class Entry(
+        val listing: MirrordTargetListing,
+        val loader: (ProgressIndicator) -> MirrordTargetListing,
+        val refreshedAt: Long
+    ) {
+        @Volatile
+        var usedAt: Long = refreshedAt
+    }
+
+    private val entries: MutableMap<TargetListingKey, Entry> = ConcurrentHashMap()
+
+    /**
+     * Set while the background refresh is running, so that refreshes do not pile up.
+     */
+    private val refreshing = AtomicBoolean(false)
+
+    private val refreshFuture: ScheduledFuture<*>
+
+    /**
+     * Invalidates cached listings when their config file is changed, moved or deleted.
+     */
+    private inner class ConfigWatch : AsyncFileListener {
+        override fun prepareChange(events: MutableList<out VFileEvent>): AsyncFileListener.ChangeApplier? {
+            val changedPaths = events.map { it.path }.toSet()
+            val isChanged = { key: TargetListingKey ->
+                key.configFile?.let { changedPaths.contains(FileUtil.toSystemIndependentName(it)) } ?: false
+            }
+            if (entries.keys.none(isChanged)) {
+                return null
+            }
+
+            return object : AsyncFileListener.ChangeApplier {
+                override fun afterVfsChange() {
+                    entries.keys.removeIf { isChanged(it) }
+                }
+            }
+        }
+    }
+
+    init {
+        Disposer.register(service, this)
+        VirtualFileManager.getInstance().addAsyncFileListener(ConfigWatch(), this)
+
+        refreshFuture = AppExecutorUtil.getAppScheduledExecutorService().scheduleWithFixedDelay(
+            { refreshDue() },
+            REFRESH_CHECK_INTERVAL_SECONDS,
+            REFRESH_CHECK_INTERVAL_SECONDS,
+            TimeUnit.SECONDS
+        )
+    }
+
+    /**
+     * Creates a key for a listing with the current kube context.
+     */
+    fun key(namespace: String?, configFile: String?, env: Map<String, String>?): TargetListingKey {
+        val kubeContext = service.kubeContext ?: service.kubeConfig.readContexts().current
+        return TargetListingKey(kubeContext, namespace, configFile, env)
+    }
+
+    /**
+     * Returns the cached listing for the given key. If there is none (or [refresh] is set), lists the targets
+     * with the given [loader] and caches the result. The [loader] is also used later to refresh the listing in the background.
+     *
+     * @param onLoaded called when the targets were listed by this call, not for cached listings or background refreshes
+     * @throws MirrordError if the loader fails
+     * @throws ProcessCanceledException if the indicator was canceled
+     */
+    fun get(
+        key: TargetListingKey,
+        refresh: Boolean,
+        indicator: ProgressIndicator,
+        onLoaded: (MirrordTargetListing) -> Unit = {},
+        loader: (ProgressIndicator) -> MirrordTargetListing
+    ): MirrordTargetListing {
+        if (!refresh) {
+            entries[key]?.let {
+                MirrordLogger.logger.debug("using cached targets listing for $key")
+                it.usedAt = System.currentTimeMillis()
+                return it.listing
+            }
+        }
+
+        val listing = loader(indicator)
+        entries[key] = Entry(listing, loader, System.currentTimeMillis())
+        onLoaded(listing)
+        return listing
+    }
+
+    /**
+     * Refreshes the listings that are older than the configured interval on a pooled thread.
+     * Listings that were not used for a long time are dropped.
+     */
+    private fun refreshDue() {
+        val interval = MirrordSettingsState.instance.mirrordState.targetsRefreshInterval
+        if (interval <= 0) {
+            return
+        }
+
+        val now = System.currentTimeMillis()
+        entries.entries.removeIf { now - it.value.usedAt > UNUSED_ENTRY_TTL_MILLIS }
+
+        val due = entries.filterValues { now - it.refreshedAt >= TimeUnit.SECONDS.toMillis(interval.toLong()) }
+        if (due.isEmpty() || !refreshing.compareAndSet(false, true)) {
+            return
+        }
+
+        ApplicationManager.getApplication().executeOnPooledThread {
+            try {
+                due.forEach { (key, entry) -> refresh(key, entry) }
+            } finally {
+                refreshing.set(false)
+            }
+        }
+    }
+
+    private fun refresh(key: TargetListingKey, entry: Entry) {
+        val indicator = EmptyProgressIndicator()
+        val timeout = AppExecutorUtil
+            .getAppScheduledExecutorService()
+            .schedule(Runnable { indicator.cancel() }, REFRESH_TIMEOUT_MINUTES, TimeUnit.MINUTES)
+
+        try {
+            MirrordLogger.logger.debug("refreshing cached targets listing for $key")
+            val listing = entry.loader(indicator)
+            val refreshed = Entry(listing, entry.loader, System.currentTimeMillis()).apply { usedAt = entry.usedAt }
+            // Do not bring back an entry that was invalidated in the meantime.
+            entries.replace(key, entry, refreshed)
+        } catch (e: ProcessCanceledException) {
+            MirrordLogger.logger.debug("refreshing cached targets listing for $key timed out")
+            entries.remove(key, entry)
+        } catch (e: Exception) {
+            MirrordLogger.logger.debug("failed to refresh cached targets listing for $key", e)
+            entries.remove(key, entry)
+        } finally {
+            timeout.cancel(false)
+        }
+    }
+
+    override fun dispose() {
+        refreshFuture.cancel(false)
+        entries.clear()
+    }
+}
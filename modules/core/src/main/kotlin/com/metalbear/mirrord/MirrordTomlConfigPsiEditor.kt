@@ -0,0 +1,200 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.JsonNode
+import com.intellij.openapi.editor.Document
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.toml.lang.psi.TomlFile
+import org.toml.lang.psi.TomlInlineTable
+import org.toml.lang.psi.TomlKey
+import org.toml.lang.psi.TomlKeyValue
+import org.toml.lang.psi.TomlTable
+
+/**
+ * Edits toml configs. The TOML PSI is used to find the edited values, the changes are made in the document,
+ * since a value can be spread over tables, dotted keys and inline tables.
+ */
+class MirrordTomlConfigPsiEditor : MirrordConfigPsiEditor {
+    /**
+     * A key value with the full path to its value.
+     */
+    private class Entry(val path: List<String>, val keyValue: TomlKeyValue)
+
+    /**
+     * A place where new key values can be added: the top level of the file, a table or an inline table.
+     */
+    private class Container(val path: List<String>, val element: PsiElement?)
+
+    /**
+     * Text replaced in the document.
+     */
+    private class Change(val range: TextRange, val text: String)
+
+    override fun accepts(file: PsiFile): Boolean = file is TomlFile
+
+    override fun apply(file: PsiFile, document: Document, edits: List<MirrordConfigEdit>): Boolean {
+        val tables = file.children.filterIsInstance<TomlTable>()
+        val entries = entries(file, tables)
+
+        val changes = edits.flatMap { edit ->
+            val value = edit.value
+            val existing = entries.find { it.path == edit.path }?.keyValue?.value
+            if (value != null && existing != null) {
+                // A single value is replaced in place.
+                return@flatMap listOf(Change(existing.textRange, MirrordConfigFormat.TOML.writeValue(value)))
+            }
+
+            val removed = removedElements(edit.path, entries, tables)
+            val removals = removed.map { element ->
+                if (element.parent is TomlInlineTable) {
+                    Change(inlineEntryRange(element, document), "")
+                } else {
+                    Change(lineRange(element, document), "")
+                }
+            }
+            value?.let { removals + insert(file, edit.path, it, entries, tables, removed) } ?: removals
+        }
+
+        // Applied from the end of the file, so that the ranges of the remaining changes stay valid.
+        // A removal and an insertion at the same offset do not overlap if the removal goes first.
+        changes
+            .sortedWith(compareByDescending<Change> { it.range.startOffset }.thenByDescending { it.range.length })
+            .forEach { document.replaceString(it.range.startOffset, it.range.endOffset, it.text) }
+        return true
+    }
+
+    /**
+     * A value is set either with a key value (possibly dotted, or in an inline table), or with a table header.
+     */
+    override fun findKey(file: PsiFile, path: List<String>): PsiElement? {
+        val tables = file.children.filterIsInstance<TomlTable>()
+        return entries(file, tables).find { it.path == path }?.keyValue?.key
+            ?: tables.find { segments(it.header.key) == path }?.header?.key
+    }
+
+    /**
+     * All key values of the file, with their full paths.
+     */
+    private fun entries(file: PsiFile, tables: List<TomlTable>): List<Entry> {
+        val entries = mutableListOf<Entry>()
+        file.children.filterIsInstance<TomlKeyValue>().forEach { collect(emptyList(), it, entries) }
+        tables.forEach { table ->
+            val tablePath = segments(table.header.key)
+            table.entries.forEach { collect(tablePath, it, entries) }
+        }
+        return entries
+    }
+
+    private fun collect(prefix: List<String>, keyValue: TomlKeyValue, entries: MutableList<Entry>) {
+        val path = prefix + segments(keyValue.key)
+        entries.add(Entry(path, keyValue))
+        (keyValue.value as? TomlInlineTable)?.entries?.forEach { collect(path, it, entries) }
+    }
+
+    private fun segments(key: TomlKey?): List<String> {
+        return key?.segments.orEmpty().map { it.text.removeSurrounding("\"").removeSurrounding("'") }
+    }
+
+    /**
+     * The tables and the key values with the path or under it.
+     * Nested elements are not returned on their own when their parent is removed.
+     */
+    private fun removedElements(path: List<String>, entries: List<Entry>, tables: List<TomlTable>): List<PsiElement> {
+        val removedTables = tables.filter { segments(it.header.key).startsWith(path) }
+        val removedEntries = entries
+            .filter { it.path.startsWith(path) }
+            .map { it.keyValue }
+            .filter { keyValue -> removedTables.none { it.textRange.contains(keyValue.textRange) } }
+        val topEntries = removedEntries.filter { keyValue ->
+            removedEntries.none { it !== keyValue && it.textRange.contains(keyValue.textRange) }
+        }
+
+        return removedTables + topEntries
+    }
+
+    /**
+     * The whole lines of the element, with the line break.
+     */
+    private fun lineRange(element: PsiElement, document: Document): TextRange {
+        val start = document.getLineStartOffset(document.getLineNumber(element.textRange.startOffset))
+        val endLine = document.getLineNumber(element.textRange.endOffset)
+        val end = if (endLine + 1 < document.lineCount) document.getLineStartOffset(endLine + 1) else document.textLength
+        return TextRange(start, end)
+    }
+
+    /**
+     * The entry of an inline table, with the comma after it (or before it, for the last entry).
+     */
+    private fun inlineEntryRange(keyValue: PsiElement, document: Document): TextRange {
+        val text = document.charsSequence
+        var end = keyValue.textRange.endOffset
+        while (end < text.length && text[end].isWhitespace()) end++
+        if (end < text.length && text[end] == ',') {
+            end++
+            while (end < text.length && text[end] == ' ') end++
+            return TextRange(keyValue.textRange.startOffset, end)
+        }
+
+        var start = keyValue.textRange.startOffset
+        while (start > 0 && text[start - 1].isWhitespace()) start--
+        if (start > 0 && text[start - 1] == ',') start--
+        return TextRange(start, keyValue.textRange.endOffset)
+    }
+
+    /**
+     * Adds the value to the deepest table or inline table that contains the path, with a dotted key relative to it.
+     * The new key value goes after the last one that is kept.
+     *
+     * @param removed elements removed by the same edit
+     */
+    private fun insert(
+        file: PsiFile,
+        path: List<String>,
+        value: JsonNode,
+        entries: List<Entry>,
+        tables: List<TomlTable>,
+        removed: List<PsiElement>
+    ): List<Change> {
+        val keptTables = tables.filter { it !in removed }
+        val containers = listOf(Container(emptyList(), null)) +
+            keptTables.map { Container(segments(it.header.key), it) } +
+            entries
+                .filter { it.keyValue.value is TomlInlineTable && removed.none { element -> element.textRange.contains(it.keyValue.textRange) } }
+                .map { Container(it.path, it.keyValue.value) }
+        val container = containers
+            .filter { path.size > it.path.size && path.startsWith(it.path) }
+            .maxBy { it.path.size }
+
+        val key = path.drop(container.path.size).joinToString(".") { tomlKey(it) }
+        val line = "$key = ${MirrordConfigFormat.TOML.writeValue(value)}"
+
+        return listOf(
+            when (val element = container.element) {
+                is TomlTable -> {
+                    val last = element.entries.lastOrNull { it !in removed } ?: element.header
+                    Change(TextRange.from(last.textRange.endOffset, 0), "\n$line")
+                }
+                is TomlInlineTable -> {
+                    val last = element.entries.lastOrNull { it !in removed }
+                    if (last == null) {
+                        Change(TextRange.from(element.textRange.startOffset + 1, 0), " $line ")
+                    } else {
+                        Change(TextRange.from(last.textRange.endOffset, 0), ", $line")
+                    }
+                }
+                // The top level key values must come before the first table.
+                else -> {
+                    val last = file.children.filterIsInstance<TomlKeyValue>().lastOrNull { it !in removed }
+                    when {
+                        last != null -> Change(TextRange.from(last.textRange.endOffset, 0), "\n$line")
+                        keptTables.isNotEmpty() -> Change(TextRange.from(keptTables.first().textRange.startOffset, 0), "$line\n\n")
+                        else -> Change(TextRange.from(file.textLength, 0), if (file.textLength == 0) "$line\n" else "\n$line\n")
+                    }
+                }
+            }
+        )
+    }
+
+    private fun List<String>.startsWith(prefix: List<String>): Boolean = size >= prefix.size && subList(0, prefix.size) == prefix
+}
@@ -0,0 +1,23 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.vfs.VirtualFile
+import com.jetbrains.jsonSchema.extension.JsonSchemaEnabler
+
+/**
+ * Enables the JSON schema support of the TOML plugin for mirrord configs, so that [MirrordSchemaFileProvider] applies to them.
+ * Matches the same files as [MirrordSchemaFileProvider.isAvailable].
+ * Registered only when both the TOML plugin and the JSON module are available.
+ */
+class MirrordTomlSchemaEnabler : JsonSchemaEnabler {
+    override fun isEnabledForFile(file: VirtualFile, project: Project?): Boolean {
+        project ?: return false
+        if (file.extension != "toml") {
+            return false
+        }
+        return MirrordConfigAPI.isConfigFilePath(file) || (!project.isDisposed && project.service<MirrordProjectService>().configApi.isProjectConfig(file))
+    }
+
+    override fun canBeSchemaFile(file: VirtualFile): Boolean = false
+}
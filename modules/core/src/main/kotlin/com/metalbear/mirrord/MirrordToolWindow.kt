@@ -0,0 +1,238 @@
+package com.metalbear.mirrord
+
+import com.intellij.icons.AllIcons
+import com.intellij.openapi.Disposable
+import com.intellij.openapi.actionSystem.ActionManager
+import com.intellij.openapi.actionSystem.AnAction
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.actionSystem.DefaultActionGroup
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.application.ModalityState
+import com.intellij.openapi.components.service
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.SimpleToolWindowPanel
+import com.intellij.openapi.wm.ToolWindow
+import com.intellij.openapi.wm.ToolWindowFactory
+import com.intellij.openapi.wm.ToolWindowManager
+import com.intellij.ui.AnimatedIcon
+import com.intellij.ui.ColoredTreeCellRenderer
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.SimpleTextAttributes
+import com.intellij.ui.content.ContentFactory
+import com.intellij.ui.treeStructure.Tree
+import com.intellij.util.Alarm
+import java.time.Duration
+import java.time.Instant
+import java.time.ZoneId
+import java.time.format.DateTimeFormatter
+import javax.swing.Icon
+import javax.swing.JTree
+import javax.swing.tree.DefaultMutableTreeNode
+import javax.swing.tree.DefaultTreeModel
+import javax.swing.tree.TreePath
+
+private val TIME_FORMAT: DateTimeFormatter = DateTimeFormatter.ofPattern("HH:mm:ss.SSS").withZone(ZoneId.systemDefault())
+
+private fun Instant.format(): String = TIME_FORMAT.format(this)
+
+private fun Duration.format(): String = "%.2fs".format(toMillis() / 1000.0)
+
+private fun statusIcon(success: Boolean?): Icon = when (success) {
+    null -> AnimatedIcon.Default.INSTANCE
+    true -> AllIcons.RunConfigurations.TestPassed
+    false -> AllIcons.RunConfigurations.TestFailed
+}
+
+/**
+ * Creates the "mirrord" tool window.
+ */
+class MirrordToolWindowFactory : ToolWindowFactory, DumbAware {
+    companion object {
+        private const val TOOL_WINDOW_ID = "mirrord"
+
+        /**
+         * Opens the mirrord tool window and selects the tab with the given title.
+         */
+        fun showTab(project: Project, title: String) {
+            val toolWindow = ToolWindowManager.getInstance(project).getToolWindow(TOOL_WINDOW_ID) ?: return
+            toolWindow.show {
+                toolWindow.contentManager.findContent(title)?.let { toolWindow.contentManager.setSelectedContent(it) }
+            }
+        }
+    }
+
+    override fun createToolWindowContent(project: Project, toolWindow: ToolWindow) {
+        val sessionsPanel = MirrordSessionsPanel(project, toolWindow.disposable)
+        val content = ContentFactory.getInstance().createContent(sessionsPanel, "Sessions", false)
+        toolWindow.contentManager.addContent(content)
+
+        val environmentPanel = MirrordEnvironmentPanel(project, toolWindow.disposable)
+        toolWindow.contentManager.addContent(ContentFactory.getInstance().createContent(environmentPanel, "Environment", false))
+
+        val portForwardPanel = MirrordPortForwardPanel(project, toolWindow.disposable)
+        toolWindow.contentManager.addContent(ContentFactory.getInstance().createContent(portForwardPanel, "Port Forwards", false))
+
+        val operatorSessionsPanel = MirrordOperatorSessionsPanel(project, toolWindow.disposable)
+        toolWindow.contentManager.addContent(ContentFactory.getInstance().createContent(operatorSessionsPanel, MirrordOperatorSessionsPanel.TITLE, false))
+
+        val operatorStatusPanel = MirrordOperatorStatusPanel(project, toolWindow.disposable)
+        toolWindow.contentManager.addContent(ContentFactory.getInstance().createContent(operatorStatusPanel, MirrordOperatorStatusPanel.TITLE, false))
+    }
+}
+
+/**
+ * Displays mirrord sessions as trees of tasks reported by the mirrord binary.
+ */
+class MirrordSessionsPanel(project: Project, parentDisposable: Disposable) : SimpleToolWindowPanel(true, true) {
+    private val manager = project.service<MirrordProjectService>().sessions
+
+    private val root = DefaultMutableTreeNode()
+
+    private val model = DefaultTreeModel(root)
+
+    private val tree = Tree(model).apply {
+        isRootVisible = false
+        showsRootHandles = true
+        cellRenderer = SessionTreeRenderer()
+        putClientProperty(AnimatedIcon.ANIMATION_IN_RENDERER_ALLOWED, true)
+    }
+
+    /**
+     * Tree nodes of the sessions and tasks that are currently displayed.
+     */
+    private val nodes: MutableMap<Any, DefaultMutableTreeNode> = HashMap()
+
+    /**
+     * Batches updates, the mirrord binary can report many tasks in a short time.
+     */
+    private val updateAlarm = Alarm(Alarm.ThreadToUse.SWING_THREAD, parentDisposable)
+
+    private inner class ClearFinishedAction : AnAction("Clear Finished Sessions", null, AllIcons.Actions.GC), DumbAware {
+        override fun actionPerformed(e: AnActionEvent) {
+            manager.clearFinished()
+        }
+    }
+
+    private class SessionTreeRenderer : ColoredTreeCellRenderer() {
+        override fun customizeCellRenderer(
+            tree: JTree,
+            value: Any?,
+            selected: Boolean,
+            expanded: Boolean,
+            leaf: Boolean,
+            row: Int,
+            hasFocus: Boolean
+        ) {
+            when (val item = (value as? DefaultMutableTreeNode)?.userObject) {
+                is MirrordSession -> {
+                    icon = statusIcon(item.success)
+                    append("Session #${item.id}")
+                    append("  ${item.target ?: "target from config or targetless"}", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                    append("  started ${item.startedAt.format()}", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                    item.finishedAt?.let {
+                        append(" (${Duration.between(item.startedAt, it).format()})", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                    }
+                }
+
+                is MirrordSessionTask -> {
+                    icon = statusIcon(item.success)
+                    append(item.name)
+                    append("  ${item.startedAt.format()}", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                    item.finishedAt?.let {
+                        append(" - ${it.format()}", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                    }
+                    item.duration?.let {
+                        append(" (${it.format()})", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                    }
+                    item.message?.let {
+                        val attributes = if (item.success == false) SimpleTextAttributes.ERROR_ATTRIBUTES else SimpleTextAttributes.REGULAR_ATTRIBUTES
+                        append("  $it", attributes)
+                    }
+                }
+            }
+        }
+    }
+
+    init {
+        val toolbar = ActionManager
+            .getInstance()
+            .createActionToolbar("MirrordSessions", DefaultActionGroup(ClearFinishedAction()), true)
+        toolbar.targetComponent = this
+
+        setToolbar(toolbar.component)
+        setContent(ScrollPaneFactory.createScrollPane(tree))
+
+        manager.addListener({ scheduleUpdate() }, parentDisposable)
+        update()
+    }
+
+    private fun scheduleUpdate() {
+        if (updateAlarm.isDisposed) {
+            return
+        }
+
+        updateAlarm.cancelAllRequests()
+        updateAlarm.addRequest({ update() }, 100, ModalityState.any())
+    }
+
+    /**
+     * Synchronizes the tree with the current state of the sessions.
+     * Sessions and tasks are only appended by the manager, so existing nodes (and their expansion state) are reused.
+     * Parents of new nodes are expanded, so that the progress of a starting session is visible.
+     */
+    private fun update() {
+        ApplicationManager.getApplication().assertIsDispatchThread()
+
+        val knownItems = nodes.keys.toSet()
+
+        val sessions = manager.sessions
+        syncChildren(root, sessions)
+        sessions.forEach { session ->
+            syncTasks(nodes[session]!!, session.tasks)
+        }
+
+        nodes.keys.retainAll(collectItems(root))
+
+        nodes
+            .filterKeys { !knownItems.contains(it) }
+            .values
+            .mapNotNull { it.parent as? DefaultMutableTreeNode }
+            .toSet()
+            .forEach { tree.expandPath(TreePath(it.path)) }
+    }
+
+    private fun syncTasks(parent: DefaultMutableTreeNode, tasks: List<MirrordSessionTask>) {
+        syncChildren(parent, tasks)
+        tasks.forEach { syncTasks(nodes[it]!!, it.children) }
+    }
+
+    /**
+     * Makes the children of [parent] display exactly the given [items], in the given order.
+     */
+    private fun syncChildren(parent: DefaultMutableTreeNode, items: List<Any>) {
+        items.forEachIndexed { index, item ->
+            val node = nodes.getOrPut(item) { DefaultMutableTreeNode(item) }
+            if (index >= parent.childCount || parent.getChildAt(index) !== node) {
+                if (node.parent != null) {
+                    model.removeNodeFromParent(node)
+                }
+                model.insertNodeInto(node, parent, index)
+            } else {
+                model.nodeChanged(node)
+            }
+        }
+
+        while (parent.childCount > items.size) {
+            model.removeNodeFromParent(parent.getChildAt(items.size) as DefaultMutableTreeNode)
+        }
+    }
+
+    private fun collectItems(node: DefaultMutableTreeNode): Set<Any> {
+        return node
+            .depthFirstEnumeration()
+            .toList()
+            .mapNotNull { (it as DefaultMutableTreeNode).userObject }
+            .toSet()
+    }
+}
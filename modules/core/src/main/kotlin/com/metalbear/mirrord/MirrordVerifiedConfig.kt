@@ -10,12 +10,19 @@ import com.intellij.util.asSafely
  * It parses the output from the command and displays warnings/errors.
  *
  * The command outputs either a `type = "Success"` or `type = "Fail"`.
+ *
+ * @param notifier used to display warnings/errors, null to only parse them
  */
-class MirrordVerifiedConfig(private val verified: String, private val notifier: MirrordNotifier) {
+class MirrordVerifiedConfig(private val verified: String, private val notifier: MirrordNotifier?) {
     /**
      * The errors when the output is `type = "Fail"`.
      */
-    private val errors: List<String>?
+    val errors: List<String>?
+
+    /**
+     * The warnings when the output is `type = "Success"`.
+     */
+    val warnings: List<String>?
 
     /**
      * `verify-config` also outputs the `MirrordConfig` that was set, when `type = "Success"`.
@@ -26,14 +33,13 @@ class MirrordVerifiedConfig(private val verified: String, private val notifier:
         val gson = Gson()
 
         this.config = gson.fromJson(this.verified, Map::class.java).let { verified ->
-            verified["warnings"].asSafely<List<String>>().also { warnings ->
-                warnings?.forEach { this.notifier.notifySimple(it, NotificationType.WARNING) }
+            this.warnings = verified["warnings"].asSafely<List<String>>().also { warnings ->
+                warnings?.forEach { this.notifier?.notifySimple(it, NotificationType.WARNING) }
             }
 
-            val errors = verified["errors"].asSafely<List<String>>().also { errors ->
-                errors?.forEach { this.notifier.notifySimple(it, NotificationType.ERROR) }
+            this.errors = verified["errors"].asSafely<List<String>>().also { errors ->
+                errors?.forEach { this.notifier?.notifySimple(it, NotificationType.ERROR) }
             }
-            this.errors = errors
 
             verified["config"].toString()
         }
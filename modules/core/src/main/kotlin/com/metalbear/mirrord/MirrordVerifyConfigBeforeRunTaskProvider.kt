@@ -0,0 +1,87 @@
+package com.metalbear.mirrord
+
+import com.intellij.execution.BeforeRunTask
+import com.intellij.execution.BeforeRunTaskProvider
+import com.intellij.execution.configurations.RunConfiguration
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.execution.target.createEnvironmentRequest
+import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
+import com.intellij.notification.NotificationType
+import com.intellij.openapi.actionSystem.DataContext
+import com.intellij.openapi.components.service
+import com.intellij.openapi.progress.ProcessCanceledException
+import com.intellij.openapi.util.Key
+import icons.MirrordIcons
+import javax.swing.Icon
+
+class MirrordVerifyConfigBeforeRunTask : BeforeRunTask<MirrordVerifyConfigBeforeRunTask>(MirrordVerifyConfigBeforeRunTaskProvider.ID)
+
+/**
+ * Before launch task that runs `mirrord verify-config` on the config the run configuration would use.
+ * Errors abort the launch, which is useful for run configurations where mirrord is started by an `ExecutionListener`
+ * and the run cannot be aborted later.
+ */
+class MirrordVerifyConfigBeforeRunTaskProvider : BeforeRunTaskProvider<MirrordVerifyConfigBeforeRunTask>() {
+    companion object {
+        val ID: Key<MirrordVerifyConfigBeforeRunTask> = Key.create("mirrord.verifyConfig")
+    }
+
+    override fun getId(): Key<MirrordVerifyConfigBeforeRunTask> = ID
+
+    override fun getName(): String = "Verify mirrord config"
+
+    override fun getIcon(): Icon = MirrordIcons.enabled
+
+    override fun createTask(runConfiguration: RunConfiguration): MirrordVerifyConfigBeforeRunTask {
+        return MirrordVerifyConfigBeforeRunTask()
+    }
+
+    override fun executeTask(
+        context: DataContext,
+        configuration: RunConfiguration,
+        environment: ExecutionEnvironment,
+        task: MirrordVerifyConfigBeforeRunTask
+    ): Boolean {
+        val project = environment.project
+        val service = project.service<MirrordProjectService>()
+
+        val options = (configuration as? RunConfigurationBase<*>)
+            ?.let { MirrordRunConfigurationOptions.get(it) }
+            ?: MirrordRunConfigurationOptions()
+        val env = MirrordRunConfigurationEnvProvider.envFor(environment)
+
+        @Suppress("UnstableApiUsage") // `createEnvironmentRequest`
+        val wsl = when (val request = createEnvironmentRequest(configuration, project)) {
+            is WslTargetEnvironmentRequest -> request.configuration.distribution
+            else -> null
+        }
+
+        return try {
+            val configPath = service.execManager.configPath(options, env, configuration as? RunConfigurationBase<*>) ?: run {
+                MirrordLogger.logger.debug("no mirrord config to verify")
+                return true
+            }
+
+            val verified = service.execManager.verifyConfig(configPath, wsl, "before-run", env)
+            verified.warnings?.forEach { service.notifier.notifySimple(it, NotificationType.WARNING) }
+
+            val errors = verified.errors.orEmpty()
+            errors.forEach { error ->
+                service
+                    .notifier
+                    .notification(error, NotificationType.ERROR)
+                    .withAction("Go to Error") { _, _ -> MirrordConfigLocator.navigate(project, configPath, error) }
+                    .fire()
+            }
+
+            errors.isEmpty()
+        } catch (e: MirrordError) {
+            e.showHelp(project)
+            false
+        } catch (e: ProcessCanceledException) {
+            service.notifier.notifySimple("mirrord config verification was cancelled", NotificationType.WARNING)
+            false
+        }
+    }
+}
@@ -0,0 +1,71 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.JsonNode
+import com.fasterxml.jackson.databind.node.JsonNodeFactory
+import com.intellij.openapi.editor.Document
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import com.intellij.psi.util.PsiTreeUtil
+import org.jetbrains.yaml.YAMLElementGenerator
+import org.jetbrains.yaml.YAMLUtil
+import org.jetbrains.yaml.psi.YAMLFile
+import org.jetbrains.yaml.psi.YAMLKeyValue
+import org.jetbrains.yaml.psi.YAMLMapping
+
+/**
+ * Edits yaml configs through the YAML PSI.
+ */
+class MirrordYamlConfigPsiEditor : MirrordConfigPsiEditor {
+    override fun accepts(file: PsiFile): Boolean = file is YAMLFile
+
+    override fun apply(file: PsiFile, document: Document, edits: List<MirrordConfigEdit>): Boolean {
+        val root = (file as YAMLFile).documents.firstOrNull()?.topLevelValue as? YAMLMapping ?: return false
+        edits.forEach { apply(root, it.path, it.value) }
+        return true
+    }
+
+    override fun findKey(file: PsiFile, path: List<String>): PsiElement? {
+        var mapping = (file as YAMLFile).documents.firstOrNull()?.topLevelValue as? YAMLMapping ?: return null
+        path.dropLast(1).forEach { name ->
+            mapping = mapping.getKeyValueByKey(name)?.value as? YAMLMapping ?: return null
+        }
+        return mapping.getKeyValueByKey(path.last())?.key
+    }
+
+    private fun apply(mapping: YAMLMapping, path: List<String>, value: JsonNode?) {
+        val name = path.first()
+        val keyValue = mapping.getKeyValueByKey(name)
+
+        if (path.size > 1) {
+            (keyValue?.value as? YAMLMapping)?.let { return apply(it, path.drop(1), value) }
+            value ?: return
+            // The parent mappings are missing, they are created together with the value.
+            val nested = path.drop(1).foldRight(value) { key, child -> JsonNodeFactory.instance.objectNode().set<JsonNode>(key, child) }
+            return mapping.putKeyValue(createKeyValue(mapping, name, nested))
+        }
+
+        if (value == null) {
+            keyValue?.let { mapping.deleteKeyValue(it) }
+        } else {
+            // Replaces the existing key value, if there is one.
+            mapping.putKeyValue(createKeyValue(mapping, name, value))
+        }
+    }
+
+    /**
+     * The nested lines of a block value are indented relative to [mapping],
+     * since the key value is inserted as is, only its first line is indented by the mapping.
+     */
+    private fun createKeyValue(mapping: YAMLMapping, name: String, value: JsonNode): YAMLKeyValue {
+        val valueText = MirrordConfigFormat.YAML.writeValue(value)
+        val text = if (value.isContainerNode) {
+            val indent = " ".repeat(YAMLUtil.getIndentToThisElement(mapping) + 2)
+            "$name:\n" + valueText.lines().joinToString("\n") { "$indent$it" }
+        } else {
+            "$name: $valueText"
+        }
+
+        val dummyFile = YAMLElementGenerator.getInstance(mapping.project).createDummyYamlWithText(text)
+        return PsiTreeUtil.collectElementsOfType(dummyFile, YAMLKeyValue::class.java).first()
+    }
+}
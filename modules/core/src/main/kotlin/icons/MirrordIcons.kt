@@ -11,4 +11,7 @@ object MirrordIcons {
 
     @JvmField
     val disabled = IconLoader.getIcon("/icons/mirrord_disabled.svg", javaClass)
+
+    @JvmField
+    val toolWindow = IconLoader.getIcon("/icons/mirrord_tool_window.svg", javaClass)
 }
@@ -0,0 +1,60 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.node.JsonNodeFactory
+import com.fasterxml.jackson.databind.node.ObjectNode
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Test
+
+internal class MirrordConfigEditTest {
+    private fun parse(text: String): ObjectNode = MirrordConfigFormat.JSON.parse("mirrord.json", text)
+
+    @Test
+    fun diffsObjectsKeyByKey() {
+        val old = parse("""{ "target": "pod/a", "feature": { "fs": "read", "network": { "incoming": "mirror" } }, "agent": { "ttl": 5 } }""")
+        val new = parse("""{ "target": "pod/b", "feature": { "fs": "read", "network": { "incoming": { "mode": "steal" } } } }""")
+
+        assertEquals(
+            listOf(
+                MirrordConfigEdit(listOf("agent"), null),
+                MirrordConfigEdit(listOf("target"), JsonNodeFactory.instance.textNode("pod/b")),
+                MirrordConfigEdit(listOf("feature", "network", "incoming"), new.path("feature").path("network").path("incoming"))
+            ),
+            MirrordConfigEdit.diff(old, new)
+        )
+    }
+
+    @Test
+    fun diffsEqualConfigsToNothing() {
+        val config = """{ "feature": { "env": { "include": ["A"] } } }"""
+
+        assertEquals(emptyList<MirrordConfigEdit>(), MirrordConfigEdit.diff(parse(config), parse(config)))
+    }
+
+    @Test
+    fun quotesTomlKeysOnlyWhenNeeded() {
+        assertEquals("log_level", tomlKey("log_level"))
+        assertEquals("\"x.y\"", tomlKey("x.y"))
+        assertEquals("\"a b\"", tomlKey("a b"))
+    }
+
+    @Test
+    fun writesTomlValues() {
+        val value = parse("""{ "mode": "steal", "ports": [80, 443], "header": "a\"b\n", "enabled": true }""")
+
+        assertEquals(
+            """{ mode = "steal", ports = [80, 443], header = "a\"b\n", enabled = true }""",
+            MirrordConfigFormat.TOML.writeValue(value)
+        )
+    }
+
+    @Test
+    fun writesJsonValuesWithTheDefaultFormatting() {
+        assertEquals("{\n    \"mode\": \"steal\"\n}", MirrordConfigFormat.JSON.writeValue(parse("""{ "mode": "steal" }""")))
+    }
+
+    @Test
+    fun readsEmptyConfigs() {
+        assertEquals(parse("{}"), MirrordConfigFormat.YAML.parse("mirrord.yaml", "# only a comment\n"))
+        assertEquals(parse("{}"), MirrordConfigFormat.TOML.parse("mirrord.toml", ""))
+    }
+}
@@ -0,0 +1,144 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.node.ObjectNode
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Assertions.assertFalse
+import org.junit.jupiter.api.Assertions.assertTrue
+import org.junit.jupiter.api.Test
+
+internal class MirrordConfigFormModelTest {
+    private fun parse(text: String): ObjectNode = MirrordConfigFormat.JSON.parse("mirrord.json", text)
+
+    @Test
+    fun readsShortForms() {
+        val model = MirrordConfigFormModel.read(
+            parse(
+                """
+                {
+                    "target": "pod/my-pod",
+                    "feature": {
+                        "network": { "incoming": "steal", "outgoing": false },
+                        "fs": "local",
+                        "env": true
+                    }
+                }
+                """
+            )
+        )
+
+        assertEquals(
+            MirrordConfigFormModel(
+                targetPath = "pod/my-pod",
+                incomingMode = "steal",
+                outgoingTcp = false,
+                outgoingUdp = false,
+                fsMode = "local",
+                envEnabled = true
+            ),
+            model
+        )
+    }
+
+    @Test
+    fun readsLongForms() {
+        val model = MirrordConfigFormModel.read(
+            parse(
+                """
+                {
+                    "target": { "path": "deployment/my-deployment", "namespace": "staging" },
+                    "agent": { "namespace": "mirrord", "log_level": "debug", "ephemeral": true },
+                    "feature": {
+                        "network": {
+                            "incoming": { "mode": "mirror", "http_filter": { "header_filter": "x-user: me" } },
+                            "outgoing": { "tcp": true, "udp": false }
+                        },
+                        "fs": { "mode": "read" },
+                        "env": { "include": ["A", "B"], "exclude": "C" }
+                    }
+                }
+                """
+            )
+        )
+
+        assertEquals(
+            MirrordConfigFormModel(
+                targetPath = "deployment/my-deployment",
+                targetNamespace = "staging",
+                agentNamespace = "mirrord",
+                agentLogLevel = "debug",
+                agentEphemeral = true,
+                incomingMode = "mirror",
+                incomingHeaderFilter = "x-user: me",
+                outgoingTcp = true,
+                outgoingUdp = false,
+                fsMode = "read",
+                envEnabled = true,
+                envInclude = "A;B",
+                envExclude = "C"
+            ),
+            model
+        )
+    }
+
+    @Test
+    fun keepsUnchangedSectionsAsTheyAre() {
+        val root = parse("""{ "target": { "path": "pod/my-pod" }, "feature": { "fs": { "mode": "read", "read_only": ["/tmp"] } } }""")
+        val previous = MirrordConfigFormModel.read(root)
+
+        previous.copy(incomingMode = "steal").applyTo(root, previous)
+
+        assertEquals(
+            parse("""{ "target": { "path": "pod/my-pod" }, "feature": { "fs": { "mode": "read", "read_only": ["/tmp"] }, "network": { "incoming": "steal" } } }"""),
+            root
+        )
+    }
+
+    @Test
+    fun editsObjectsKeyByKey() {
+        val root = parse("""{ "feature": { "network": { "outgoing": { "tcp": true, "udp": true, "ignore_localhost": true } } } }""")
+        val previous = MirrordConfigFormModel.read(root)
+
+        previous.copy(outgoingUdp = false).applyTo(root, previous)
+
+        assertEquals(
+            parse("""{ "feature": { "network": { "outgoing": { "tcp": true, "udp": false, "ignore_localhost": true } } } }"""),
+            root
+        )
+    }
+
+    @Test
+    fun removesSectionsLeftEmpty() {
+        val root = parse("""{ "agent": { "log_level": "debug" }, "feature": { "fs": "local" } }""")
+        val previous = MirrordConfigFormModel.read(root)
+
+        previous.copy(agentLogLevel = null, fsMode = null).applyTo(root, previous)
+
+        assertEquals(parse("{}"), root)
+    }
+
+    @Test
+    fun writesTargetNamespaceInTheLongForm() {
+        val root = parse("""{ "target": "pod/my-pod" }""")
+        val previous = MirrordConfigFormModel.read(root)
+
+        previous.copy(targetNamespace = "staging").applyTo(root, previous)
+
+        assertEquals(parse("""{ "target": { "path": "pod/my-pod", "namespace": "staging" } }"""), root)
+    }
+
+    @Test
+    fun detectsEnvOptionsOutsideTheForm() {
+        assertTrue(MirrordConfigFormModel.hasEnvOptionsOutsideForm(parse("""{ "feature": { "env": { "include": "A", "override": { "B": "1" } } } }""")))
+        assertFalse(MirrordConfigFormModel.hasEnvOptionsOutsideForm(parse("""{ "feature": { "env": { "include": "A" } } }""")))
+    }
+
+    @Test
+    fun disablesEnvWithoutLosingOptionsOutsideTheForm() {
+        val root = parse("""{ "feature": { "env": { "include": "A", "override": { "B": "1" } } } }""")
+        val previous = MirrordConfigFormModel.read(root)
+
+        previous.copy(envEnabled = false, envInclude = null).applyTo(root, previous)
+
+        assertEquals(parse("""{ "feature": { "env": { "override": { "B": "1" } } } }"""), root)
+    }
+}
@@ -0,0 +1,50 @@
+package com.metalbear.mirrord
+
+import com.intellij.psi.PsiFile
+import com.intellij.testFramework.fixtures.BasePlatformTestCase
+
+internal class MirrordConfigLocatorTest : BasePlatformTestCase() {
+    private fun keyText(editor: MirrordConfigPsiEditor, file: PsiFile, path: String): String? {
+        return editor.findKey(file, path.split('.'))?.text
+    }
+
+    fun testFindsRangeFromPosition() {
+        val file = myFixture.configureByText("mirrord.json", "{\n  \"feature\": {\n    \"fs\": 1\n  }\n}\n")
+        val message = "invalid type: integer `1`, expected a string at line 3 column 11"
+
+        val range = MirrordConfigLocator.findRange(file, myFixture.editor.document, message)
+
+        assertEquals("1", range?.let { file.text.substring(it.startOffset, it.endOffset) })
+    }
+
+    fun testFindsJsonKey() {
+        val file = myFixture.configureByText("mirrord.json", "{ \"feature\": { \"network\": { \"incoming\": \"steal\" } } }")
+        val editor = MirrordJsonConfigPsiEditor()
+
+        assertEquals("\"incoming\"", keyText(editor, file, "feature.network.incoming"))
+        assertNull(keyText(editor, file, "feature.fs"))
+        assertNull(keyText(editor, file, "feature.network.incoming.mode"))
+    }
+
+    fun testFindsYamlKey() {
+        val file = myFixture.configureByText("mirrord.yaml", "feature:\n  network:\n    incoming: steal\n")
+        val editor = MirrordYamlConfigPsiEditor()
+
+        assertEquals("incoming", keyText(editor, file, "feature.network.incoming"))
+        assertNull(keyText(editor, file, "feature.fs"))
+    }
+
+    fun testFindsTomlKey() {
+        val file = myFixture.configureByText(
+            "mirrord.toml",
+            "target = \"deploy/app\"\n\n[feature.network]\nincoming = { mode = \"steal\" }\n\n[agent]\nttl = 30\n"
+        )
+        val editor = MirrordTomlConfigPsiEditor()
+
+        assertEquals("target", keyText(editor, file, "target"))
+        assertEquals("mode", keyText(editor, file, "feature.network.incoming.mode"))
+        assertEquals("feature.network", keyText(editor, file, "feature.network"))
+        assertEquals("ttl", keyText(editor, file, "agent.ttl"))
+        assertNull(keyText(editor, file, "agent.namespace"))
+    }
+}
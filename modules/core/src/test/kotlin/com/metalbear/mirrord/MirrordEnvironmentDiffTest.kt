@@ -0,0 +1,43 @@
+package com.metalbear.mirrord
+
+import com.metalbear.mirrord.MirrordEnvironmentDiff.Change
+import com.metalbear.mirrord.MirrordEnvironmentDiff.Entry
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Test
+
+internal class MirrordEnvironmentDiffTest {
+    private fun execution(environment: Map<String, String>, envToUnset: List<String>? = null): MirrordExecution {
+        return MirrordExecution(environment.toMutableMap(), null, envToUnset, null)
+    }
+
+    @Test
+    fun classifiesChanges() {
+        val diff = MirrordEnvironmentDiff.compute(
+            mapOf("SAME" to "1", "PORT" to "8080", "HOME_DIR" to "/home/me"),
+            execution(mapOf("SAME" to "1", "PORT" to "80", "DB_HOST" to "db"), listOf("HOME_DIR"))
+        )
+
+        assertEquals(
+            listOf(
+                Entry("DB_HOST", Change.ADDED, null, "db"),
+                Entry("HOME_DIR", Change.UNSET, "/home/me", null),
+                Entry("PORT", Change.OVERRIDDEN, "8080", "80")
+            ),
+            diff.entries
+        )
+    }
+
+    @Test
+    fun treatsAllVariablesAsAddedWithoutLocalEnvironment() {
+        val diff = MirrordEnvironmentDiff.compute(null, execution(mapOf("B" to "2", "A" to "1")))
+
+        assertEquals(listOf(Entry("A", Change.ADDED, null, "1"), Entry("B", Change.ADDED, null, "2")), diff.entries)
+    }
+
+    @Test
+    fun reportsUnsetVariablesThatWereNotSetLocally() {
+        val diff = MirrordEnvironmentDiff.compute(emptyMap(), execution(emptyMap(), listOf("NOT_SET")))
+
+        assertEquals(listOf(Entry("NOT_SET", Change.UNSET, null, null)), diff.entries)
+    }
+}
@@ -0,0 +1,43 @@
+package com.metalbear.mirrord
+
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Test
+
+internal class MirrordEnvironmentExportTest {
+    @Test
+    fun skipsSecretsAndMirrordInternals() {
+        val export = MirrordEnvironmentExport.of(
+            MirrordExecution(
+                mutableMapOf(
+                    "DB_HOST" to "db",
+                    "API_TOKEN" to "hunter2",
+                    "MIRRORD_AGENT_ADDR" to "127.0.0.1",
+                    "LD_PRELOAD" to "/tmp/libmirrord_layer.so",
+                    "APP_PORT" to "80"
+                ),
+                null,
+                listOf("B_VAR", "A_VAR"),
+                null
+            )
+        )
+
+        assertEquals(mapOf("APP_PORT" to "80", "DB_HOST" to "db"), export.variables)
+        assertEquals(listOf("APP_PORT", "DB_HOST"), export.variables.keys.toList())
+        assertEquals(listOf("A_VAR", "B_VAR"), export.unset)
+        assertEquals(listOf("API_TOKEN"), export.skippedSecrets)
+    }
+
+    @Test
+    fun escapesDotEnvValues() {
+        val export = MirrordEnvironmentExport(mapOf("A" to "plain", "B" to "say \"hi\"\nback\\slash"), listOf("C"), emptyList())
+
+        assertEquals("A=\"plain\"\nB=\"say \\\"hi\\\"\\nback\\\\slash\"\n", export.toDotEnv())
+    }
+
+    @Test
+    fun quotesShellExportsAndUnsetsVariables() {
+        val export = MirrordEnvironmentExport(mapOf("A" to "it's"), listOf("C"), emptyList())
+
+        assertEquals("export A='it'\\''s'\nunset C\n", export.toShellExports())
+    }
+}
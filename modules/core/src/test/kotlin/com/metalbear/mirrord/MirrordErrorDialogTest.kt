@@ -0,0 +1,49 @@
+package com.metalbear.mirrord
+
+import com.fasterxml.jackson.databind.ObjectMapper
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Assertions.assertFalse
+import org.junit.jupiter.api.Test
+
+internal class MirrordErrorDialogTest {
+    @Test
+    fun redactsSecretsAndSections() {
+        val config = """
+            {
+              "target": "deploy/app",
+              "operator": true,
+              "agent": { "api_key": "hunter2", "ttl": 30 },
+              "feature": {
+                "env": { "override": { "DB_HOST": "db" }, "include": ["A", "B"] },
+                "network": { "incoming": { "http_filter": { "header_filter": "x-user: me" } } }
+              }
+            }
+        """.trimIndent()
+
+        val sanitized = ObjectMapper().readTree(MirrordErrorDialog.sanitizeConfig("mirrord.json", config))
+
+        assertEquals("deploy/app", sanitized.at("/target").asText())
+        assertEquals(true, sanitized.at("/operator").asBoolean())
+        assertEquals("<redacted>", sanitized.at("/agent/api_key").asText())
+        assertEquals(30, sanitized.at("/agent/ttl").asInt())
+        assertEquals("<redacted>", sanitized.at("/feature/env/override/DB_HOST").asText())
+        assertEquals("<redacted>", sanitized.at("/feature/env/include/1").asText())
+        assertEquals("<redacted>", sanitized.at("/feature/network/incoming/http_filter/header_filter").asText())
+    }
+
+    @Test
+    fun redactsYamlAndToml() {
+        val yaml = MirrordErrorDialog.sanitizeConfig("mirrord.yaml", "agent:\n  password: hunter2\n")
+        val toml = MirrordErrorDialog.sanitizeConfig("mirrord.toml", "[agent]\npassword = \"hunter2\"\n")
+
+        assertFalse(yaml.contains("hunter2"))
+        assertFalse(toml.contains("hunter2"))
+    }
+
+    @Test
+    fun omitsUnparsableConfig() {
+        val sanitized = MirrordErrorDialog.sanitizeConfig("mirrord.json", "{ \"token\": \"hunter2\"")
+
+        assertEquals("The config file could not be parsed, it is not included.", sanitized)
+    }
+}
@@ -0,0 +1,73 @@
+package com.metalbear.mirrord
+
+import com.google.gson.Gson
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Assertions.assertFalse
+import org.junit.jupiter.api.Assertions.assertNull
+import org.junit.jupiter.api.Assertions.assertTrue
+import org.junit.jupiter.api.Test
+import java.time.Duration
+import java.time.LocalDate
+
+internal class MirrordOperatorStatusTest {
+    private fun license(expireAt: String?) = MirrordOperatorStatus.License("license", "org", expireAt, null)
+
+    @Test
+    fun parsesStatus() {
+        val json = """
+            {
+              "spec": {
+                "operator_version": "3.90.0",
+                "license": { "name": "license", "organization": "org", "expire_at": "2030-01-31", "max_seats": 10 }
+              },
+              "status": {
+                "sessions": [
+                  {
+                    "id": "A1",
+                    "duration_secs": 90,
+                    "user": "k8s-user/me@host",
+                    "target": "deploy/app",
+                    "namespace": "default",
+                    "locked_ports": [[80, "steal", "x-user: me"], [8080, "steal", null]]
+                  }
+                ],
+                "statistics": { "dau": 2, "mau": 5 }
+              }
+            }
+        """.trimIndent()
+
+        val status = Gson().fromJson(json, MirrordOperatorStatus::class.java)
+
+        assertEquals("3.90.0", status.spec?.operatorVersion)
+        assertEquals(LocalDate.of(2030, 1, 31), status.spec?.license?.expiryDate)
+        assertEquals(10, status.spec?.license?.maxSeats)
+        assertEquals(5, status.status?.statistics?.monthlyActiveUsers)
+
+        val session = status.sessions.single()
+        assertEquals(Duration.ofSeconds(90), session.age)
+        assertEquals("80 (steal, x-user: me), 8080 (steal)", session.lockedPortsDescription())
+    }
+
+    @Test
+    fun comparesVersions() {
+        val status = MirrordOperatorStatus(MirrordOperatorStatus.Spec("3.90.0", null), null)
+
+        assertEquals(true, status.isCompatibleWith("3.90.0"))
+        assertEquals(true, status.isCompatibleWith("3.100.0"))
+        assertEquals(false, status.isCompatibleWith("3.89.1"))
+        assertNull(status.isCompatibleWith(null))
+        assertNull(MirrordOperatorStatus(null, null).isCompatibleWith("3.90.0"))
+    }
+
+    @Test
+    fun warnsAboutExpiry() {
+        val today = LocalDate.now()
+
+        assertTrue(license(today.plusDays(3).toString()).expiresSoon)
+        assertTrue(license(today.minusDays(1).toString()).expiresSoon)
+        assertFalse(license(today.plusDays(60).toString()).expiresSoon)
+        assertFalse(license(null).expiresSoon)
+        assertFalse(license("soon").expiresSoon)
+        assertEquals(LocalDate.of(2030, 1, 31), license("2030-01-31T00:00:00Z").expiryDate)
+    }
+}
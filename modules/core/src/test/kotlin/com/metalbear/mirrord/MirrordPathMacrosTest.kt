@@ -0,0 +1,45 @@
+package com.metalbear.mirrord
+
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.testFramework.fixtures.BasePlatformTestCase
+import java.nio.file.Paths
+
+internal class MirrordPathMacrosTest : BasePlatformTestCase() {
+    private fun expand(path: String): String {
+        return FileUtil.toSystemIndependentName(MirrordPathMacros.expandConfigPath(project, null, path))
+    }
+
+    private val basePath: String
+        get() = FileUtil.toSystemIndependentName(project.basePath!!)
+
+    fun testResolvesRelativePathAgainstProject() {
+        assertEquals("$basePath/.mirrord/mirrord.json", expand(".mirrord/../.mirrord/mirrord.json"))
+    }
+
+    fun testNormalizesAbsolutePath() {
+        assertEquals("/tmp/mirrord.json", expand("/tmp/configs/../mirrord.json"))
+    }
+
+    fun testExpandsUserHome() {
+        val home = FileUtil.toSystemIndependentName(Paths.get(System.getProperty("user.home")).normalize().toString())
+
+        assertEquals("$home/mirrord.json", expand("\$USER_HOME\$/mirrord.json"))
+    }
+
+    fun testFailsOnUnknownMacro() {
+        val error = try {
+            expand("\$NoSuchMacro\$/mirrord.json")
+            null
+        } catch (e: MirrordError) {
+            e
+        }
+
+        assertTrue(error?.richMessage?.contains("`\$NoSuchMacro\$`") ?: false)
+    }
+
+    fun testRebasesRelativePath() {
+        val rebased = MirrordPathMacros.rebaseConfigPath(project, null, "configs/mirrord.json", "$basePath/other/mirrord.json")
+
+        assertEquals("other/mirrord.json", rebased)
+    }
+}
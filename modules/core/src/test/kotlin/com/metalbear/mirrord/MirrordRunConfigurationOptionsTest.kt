@@ -0,0 +1,61 @@
+package com.metalbear.mirrord
+
+import org.jdom.Element
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Assertions.assertFalse
+import org.junit.jupiter.api.Assertions.assertNull
+import org.junit.jupiter.api.Assertions.assertTrue
+import org.junit.jupiter.api.Test
+
+internal class MirrordRunConfigurationOptionsTest {
+    @Test
+    fun writesNothingForDefaultOptions() {
+        val element = Element("configuration")
+
+        assertFalse(MirrordRunConfigurationOptions.write(MirrordRunConfigurationOptions(), element))
+        assertTrue(element.children.isEmpty())
+    }
+
+    @Test
+    fun readsWrittenOptions() {
+        val options = MirrordRunConfigurationOptions(
+            enableMode = MirrordRunConfigurationOptions.EnableMode.ENABLED,
+            configFile = "\$ProjectFileDir\$/.mirrord/mirrord.json",
+            targetOverride = "deployment/my-deployment",
+            envPrecedence = MirrordRunConfigurationOptions.EnvPrecedence.LOCAL,
+            rememberedTarget = "pod/my-pod/container/main",
+            rememberedNamespace = "staging",
+            targetMode = MirrordRunConfigurationOptions.TargetMode.REMEMBERED
+        )
+        val element = Element("configuration")
+
+        assertTrue(MirrordRunConfigurationOptions.write(options, element))
+        assertEquals(options, MirrordRunConfigurationOptions.read(element))
+    }
+
+    @Test
+    fun readsDefaultOptionsWithoutElement() {
+        assertEquals(MirrordRunConfigurationOptions(), MirrordRunConfigurationOptions.read(Element("configuration")))
+    }
+
+    @Test
+    fun replacesUnknownValuesWithDefaults() {
+        val element = Element("configuration").addContent(
+            Element("mirrord")
+                .setAttribute("enableMode", "SOMETIMES")
+                .setAttribute("targetMode", "REMEMBERED")
+                .setAttribute("rememberedTarget", "pod/my-pod")
+        )
+
+        val options = MirrordRunConfigurationOptions.read(element)
+
+        assertEquals(MirrordRunConfigurationOptions.EnableMode.DEFAULT, options.enableMode)
+        assertEquals(MirrordRunConfigurationOptions.TargetMode.REMEMBERED, options.targetMode)
+        assertEquals("pod/my-pod", options.targetToUse)
+    }
+
+    @Test
+    fun usesRememberedTargetOnlyInRememberedMode() {
+        assertNull(MirrordRunConfigurationOptions(rememberedTarget = "pod/my-pod").targetToUse)
+    }
+}
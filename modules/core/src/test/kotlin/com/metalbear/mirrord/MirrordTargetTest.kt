@@ -0,0 +1,76 @@
+package com.metalbear.mirrord
+
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Assertions.assertFalse
+import org.junit.jupiter.api.Assertions.assertNull
+import org.junit.jupiter.api.Assertions.assertTrue
+import org.junit.jupiter.api.Test
+
+internal class MirrordTargetTest {
+    @Test
+    fun parsesPathWithoutContainer() {
+        assertEquals(Triple("pod", "my-pod", null), MirrordTarget.parsePath("pod/my-pod"))
+    }
+
+    @Test
+    fun parsesPathWithContainer() {
+        assertEquals(Triple("deployment", "my-deployment", "main"), MirrordTarget.parsePath("deployment/my-deployment/container/main"))
+    }
+
+    @Test
+    fun rejectsInvalidPaths() {
+        assertNull(MirrordTarget.parsePath("targetless"))
+        assertNull(MirrordTarget.parsePath("pod/"))
+        assertNull(MirrordTarget.parsePath("/my-pod"))
+    }
+
+    @Test
+    fun groupsContainersOfTheSameTarget() {
+        val targets = MirrordTarget.fromFound(
+            listOf(
+                FoundTarget("pod/my-pod/container/main", true),
+                FoundTarget("pod/my-pod/container/sidecar", true),
+                FoundTarget("deployment/my-deployment", null)
+            ),
+            "default"
+        )
+
+        assertEquals(
+            listOf(
+                MirrordTarget("pod", "my-pod", "default", listOf("main", "sidecar"), true),
+                MirrordTarget("deployment", "my-deployment", "default", emptyList(), true)
+            ),
+            targets
+        )
+    }
+
+    @Test
+    fun targetIsAvailableIfAnyContainerIs() {
+        val targets = MirrordTarget.fromFound(
+            listOf(
+                FoundTarget("pod/my-pod/container/main", false),
+                FoundTarget("pod/my-pod/container/sidecar", true),
+                FoundTarget("pod/stopped-pod", false)
+            ),
+            null
+        )
+
+        assertTrue(targets.single { it.name == "my-pod" }.available)
+        assertFalse(targets.single { it.name == "stopped-pod" }.available)
+    }
+
+    @Test
+    fun skipsInvalidPaths() {
+        assertEquals(emptyList<MirrordTarget>(), MirrordTarget.fromFound(listOf(FoundTarget("invalid", true)), null))
+    }
+
+    @Test
+    fun matchesPathsOfTheTargetAndItsContainers() {
+        val target = MirrordTarget("pod", "my-pod", null, listOf("main"), true)
+
+        assertTrue(target.matches("pod/my-pod"))
+        assertTrue(target.matches("pod/my-pod/container/main"))
+        assertFalse(target.matches("pod/my-pod-2"))
+        assertEquals("pod/my-pod/container/main", target.pathWithContainer("main"))
+    }
+}
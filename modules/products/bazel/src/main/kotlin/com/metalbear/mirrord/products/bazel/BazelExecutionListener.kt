@@ -5,6 +5,7 @@ import com.google.idea.blaze.base.run.state.BlazeCommandRunConfigurationCommonSt
 import com.google.idea.blaze.base.scope.BlazeContext
 import com.google.idea.blaze.base.settings.Blaze
 import com.intellij.execution.ExecutionListener
+import com.intellij.execution.configurations.RunConfigurationBase
 import com.intellij.execution.process.ProcessHandler
 import com.intellij.execution.runners.ExecutionEnvironment
 import com.intellij.execution.target.createEnvironmentRequest
@@ -73,6 +74,7 @@ class BazelExecutionListener : ExecutionListener {
         try {
             service.execManager.wrapper("bazel", originalEnv).apply {
                 this.wsl = wsl
+                this.configuration = env.runProfile as? RunConfigurationBase<*>
                 this.executable = binaryToPatch
             }.start()?.let { executionInfo ->
                 MirrordLogger.logger.debug("[${this.javaClass.name}] processStartScheduled: adding ${executionInfo.environment.size} environment variables")
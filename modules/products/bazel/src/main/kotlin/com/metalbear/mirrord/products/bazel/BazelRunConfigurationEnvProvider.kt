@@ -0,0 +1,17 @@
+package com.metalbear.mirrord.products.bazel
+
+import com.google.idea.blaze.base.run.BlazeCommandRunConfiguration
+import com.google.idea.blaze.base.run.state.BlazeCommandRunConfigurationCommonState
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.metalbear.mirrord.MirrordRunConfigurationEnvProvider
+
+/**
+ * Bazel keeps the user environment in the handler state, same as read by [BazelExecutionListener].
+ */
+class BazelRunConfigurationEnvProvider : MirrordRunConfigurationEnvProvider {
+    override fun env(environment: ExecutionEnvironment): Map<String, String>? {
+        val runProfile = environment.runProfile as? BlazeCommandRunConfiguration ?: return null
+        val state = runProfile.handler.state as? BlazeCommandRunConfigurationCommonState ?: return null
+        return state.userEnvVarsState.data.envs
+    }
+}
@@ -10,11 +10,15 @@ import com.intellij.execution.configurations.RunnerSettings
 import com.intellij.execution.target.TargetedCommandLineBuilder
 import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
 import com.intellij.openapi.components.service
+import com.intellij.openapi.options.SettingsEditor
 import com.intellij.openapi.util.SystemInfo
 import com.metalbear.mirrord.MirrordError
 import com.metalbear.mirrord.MirrordLogger
 import com.metalbear.mirrord.MirrordPathManager
 import com.metalbear.mirrord.MirrordProjectService
+import com.metalbear.mirrord.MirrordRunConfigurationEditor
+import com.metalbear.mirrord.MirrordRunConfigurationOptions
+import org.jdom.Element
 import java.nio.file.Paths
 
 class GolandRunConfigurationExtension : GoRunConfigurationExtension() {
@@ -30,6 +34,22 @@ class GolandRunConfigurationExtension : GoRunConfigurationExtension() {
         return true
     }
 
+    override fun <P : GoRunConfigurationBase<*>> createEditor(configuration: P): SettingsEditor<P> {
+        return MirrordRunConfigurationEditor(configuration.project)
+    }
+
+    override fun getEditorTitle(): String {
+        return MirrordRunConfigurationEditor.TITLE
+    }
+
+    override fun readExternal(runConfiguration: GoRunConfigurationBase<*>, element: Element) {
+        MirrordRunConfigurationOptions.readExternal(runConfiguration, element)
+    }
+
+    override fun writeExternal(runConfiguration: GoRunConfigurationBase<*>, element: Element) {
+        MirrordRunConfigurationOptions.writeExternal(runConfiguration, element)
+    }
+
     override fun patchCommandLine(
         configuration: GoRunConfigurationBase<*>,
         runnerSettings: RunnerSettings?,
@@ -51,6 +71,7 @@ class GolandRunConfigurationExtension : GoRunConfigurationExtension() {
 
             service.execManager.wrapper("goland", configuration.getCustomEnvironment()).apply {
                 this.wsl = wsl
+                this.configuration = configuration
             }.start()?.let { executionInfo ->
 
                 for (entry in executionInfo.environment.entries.iterator()) {
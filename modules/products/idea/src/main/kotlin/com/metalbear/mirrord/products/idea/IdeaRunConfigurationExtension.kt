@@ -13,11 +13,15 @@ import com.intellij.execution.target.createEnvironmentRequest
 import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
 import com.intellij.openapi.components.service
 import com.intellij.openapi.externalSystem.service.execution.ExternalSystemRunConfiguration
+import com.intellij.openapi.options.SettingsEditor
 import com.intellij.openapi.project.Project
 import com.intellij.openapi.util.Key
 import com.metalbear.mirrord.CONFIG_ENV_NAME
 import com.metalbear.mirrord.MirrordLogger
 import com.metalbear.mirrord.MirrordProjectService
+import com.metalbear.mirrord.MirrordRunConfigurationEditor
+import com.metalbear.mirrord.MirrordRunConfigurationOptions
+import org.jdom.Element
 import java.util.concurrent.ConcurrentHashMap
 
 class IdeaRunConfigurationExtension : RunConfigurationExtension() {
@@ -43,6 +47,22 @@ class IdeaRunConfigurationExtension : RunConfigurationExtension() {
         return true
     }
 
+    override fun <P : RunConfigurationBase<*>> createEditor(configuration: P): SettingsEditor<P> {
+        return MirrordRunConfigurationEditor(configuration.project)
+    }
+
+    override fun getEditorTitle(): String {
+        return MirrordRunConfigurationEditor.TITLE
+    }
+
+    override fun readExternal(runConfiguration: RunConfigurationBase<*>, element: Element) {
+        MirrordRunConfigurationOptions.readExternal(runConfiguration, element)
+    }
+
+    override fun writeExternal(runConfiguration: RunConfigurationBase<*>, element: Element) {
+        MirrordRunConfigurationOptions.writeExternal(runConfiguration, element)
+    }
+
     private fun <T : RunConfigurationBase<*>> getMirrordConfigPath(configuration: T, params: JavaParameters): String? {
         return params.env[CONFIG_ENV_NAME] ?: if (configuration is ExternalSystemRunConfiguration) {
             val ext = configuration as ExternalSystemRunConfiguration
@@ -74,6 +94,7 @@ class IdeaRunConfigurationExtension : RunConfigurationExtension() {
 
         service.execManager.wrapper("idea", extraEnv).apply {
             this.wsl = wsl
+            this.configuration = configuration
         }.start()?.let { executionInfo ->
             val mirrordEnv = executionInfo.environment + mapOf(Pair("MIRRORD_DETECT_DEBUGGER_PORT", "javaagent"))
             params.env = params.env + mirrordEnv
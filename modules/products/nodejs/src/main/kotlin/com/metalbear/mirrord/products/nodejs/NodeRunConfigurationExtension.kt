@@ -1,5 +1,6 @@
 package com.metalbear.mirrord.products.nodejs
 
+import com.intellij.execution.configurations.RunConfigurationBase
 import com.intellij.execution.runners.ExecutionEnvironment
 import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
 import com.intellij.javascript.nodejs.execution.AbstractNodeTargetRunProfile
@@ -10,22 +11,26 @@ import com.intellij.openapi.components.service
 import com.intellij.openapi.options.SettingsEditor
 import com.jetbrains.nodejs.run.NodeJsRunConfiguration
 import com.metalbear.mirrord.MirrordProjectService
-import javax.swing.JPanel
+import com.metalbear.mirrord.MirrordRunConfigurationEditor
+import com.metalbear.mirrord.MirrordRunConfigurationOptions
+import org.jdom.Element
 
 class NodeRunConfigurationExtension : AbstractNodeRunConfigurationExtension() {
 
     override fun <P : AbstractNodeTargetRunProfile> createEditor(configuration: P): SettingsEditor<P> {
-        return object : SettingsEditor<P>() {
-            override fun resetEditorFrom(s: P) {}
+        return MirrordRunConfigurationEditor((configuration as? RunConfigurationBase<*>)?.project)
+    }
 
-            override fun applyEditorTo(s: P) {}
+    override fun getEditorTitle(): String {
+        return MirrordRunConfigurationEditor.TITLE
+    }
 
-            override fun createEditor() = JPanel()
-        }
+    override fun readExternal(runConfiguration: AbstractNodeTargetRunProfile, element: Element) {
+        (runConfiguration as? RunConfigurationBase<*>)?.let { MirrordRunConfigurationOptions.readExternal(it, element) }
     }
 
-    override fun getEditorTitle(): String? {
-        return null
+    override fun writeExternal(runConfiguration: AbstractNodeTargetRunProfile, element: Element) {
+        (runConfiguration as? RunConfigurationBase<*>)?.let { MirrordRunConfigurationOptions.writeExternal(it, element) }
     }
 
     override fun createLaunchSession(configuration: AbstractNodeTargetRunProfile, environment: ExecutionEnvironment): NodeRunConfigurationLaunchSession {
@@ -47,6 +52,7 @@ class NodeRunConfigurationExtension : AbstractNodeRunConfigurationExtension() {
 
                 service.execManager.wrapper("nodejs", extraEnvVars).apply {
                     this.wsl = wsl
+                    this.configuration = configuration as? RunConfigurationBase<*>
                 }.start()?.let { executionInfo ->
                     executionInfo.environment.forEach { (key, value) ->
                         targetRun.commandLineBuilder.addEnvironmentVariable(key, value)
@@ -30,6 +30,7 @@ class PythonCommandLineProvider : PythonCommandLineTargetEnvironmentProvider {
 
             service.execManager.wrapper("pycharm", runParams.getEnvs()).apply {
                 this.wsl = wsl
+                this.configuration = runParams
             }.start()?.let { executionInfo ->
                 for (entry in executionInfo.environment.entries.iterator()) {
                     pythonExecution.addEnvironmentVariable(entry.key, entry.value)
@@ -5,9 +5,13 @@ import com.intellij.execution.configurations.RunnerSettings
 import com.intellij.execution.target.createEnvironmentRequest
 import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
 import com.intellij.openapi.components.service
+import com.intellij.openapi.options.SettingsEditor
 import com.jetbrains.python.run.AbstractPythonRunConfiguration
 import com.jetbrains.python.run.PythonRunConfigurationExtension
 import com.metalbear.mirrord.MirrordProjectService
+import com.metalbear.mirrord.MirrordRunConfigurationEditor
+import com.metalbear.mirrord.MirrordRunConfigurationOptions
+import org.jdom.Element
 
 class PythonRunConfigurationExtension : PythonRunConfigurationExtension() {
     override fun isApplicableFor(configuration: AbstractPythonRunConfiguration<*>): Boolean {
@@ -21,6 +25,22 @@ class PythonRunConfigurationExtension : PythonRunConfigurationExtension() {
         return true
     }
 
+    override fun <P : AbstractPythonRunConfiguration<*>> createEditor(configuration: P): SettingsEditor<P> {
+        return MirrordRunConfigurationEditor(configuration.project)
+    }
+
+    override fun getEditorTitle(): String {
+        return MirrordRunConfigurationEditor.TITLE
+    }
+
+    override fun readExternal(runConfiguration: AbstractPythonRunConfiguration<*>, element: Element) {
+        MirrordRunConfigurationOptions.readExternal(runConfiguration, element)
+    }
+
+    override fun writeExternal(runConfiguration: AbstractPythonRunConfiguration<*>, element: Element) {
+        MirrordRunConfigurationOptions.writeExternal(runConfiguration, element)
+    }
+
     override fun patchCommandLine(
         configuration: AbstractPythonRunConfiguration<*>,
         runnerSettings: RunnerSettings?,
@@ -38,6 +58,7 @@ class PythonRunConfigurationExtension : PythonRunConfigurationExtension() {
 
         service.execManager.wrapper("pycharm", currentEnv).apply {
             this.wsl = wsl
+            this.configuration = configuration
         }.start()?.let { executionInfo ->
             for (entry in executionInfo.environment.entries.iterator()) {
                 currentEnv[entry.key] = entry.value
@@ -1,6 +1,5 @@
 package com.metalbear.mirrord.products.rider
 
-import com.intellij.execution.RunManager
 import com.intellij.execution.configurations.GeneralCommandLine
 import com.intellij.execution.process.ProcessInfo
 import com.intellij.execution.process.ProcessListener
@@ -13,6 +12,7 @@ import com.jetbrains.rider.run.PatchCommandLineExtension
 import com.jetbrains.rider.run.WorkerRunInfo
 import com.jetbrains.rider.runtime.DotNetRuntime
 import com.metalbear.mirrord.MirrordProjectService
+import com.metalbear.mirrord.MirrordStartingRuns
 import org.jetbrains.concurrency.Promise
 import org.jetbrains.concurrency.resolvedPromise
 
@@ -20,7 +20,9 @@ class RiderPatchCommandLineExtension : PatchCommandLineExtension {
     private fun patchCommandLine(commandLine: GeneralCommandLine, project: Project) {
         val service = project.service<MirrordProjectService>()
 
-        val wsl = RunManager.getInstance(project).selectedConfiguration?.configuration?.let {
+        // Not the selected run configuration, which may have changed since the launch.
+        val configuration = project.service<MirrordStartingRuns>().current()
+        val wsl = configuration?.let {
             @Suppress("UnstableApiUsage") // `createEnvironmentRequest`
             when (val request = createEnvironmentRequest(it, project)) {
                 is WslTargetEnvironmentRequest -> request.configuration.distribution!!
@@ -30,6 +32,7 @@ class RiderPatchCommandLineExtension : PatchCommandLineExtension {
 
         service.execManager.wrapper("rider", commandLine.environment).apply {
             this.wsl = wsl
+            this.configuration = configuration
         }.start()?.let { executionInfo ->
             for (entry in executionInfo.environment.entries.iterator()) {
                 commandLine.withEnvironment(entry.key, entry.value)
@@ -7,9 +7,13 @@ import com.intellij.execution.configurations.RunnerSettings
 import com.intellij.execution.target.createEnvironmentRequest
 import com.intellij.execution.wsl.target.WslTargetEnvironmentRequest
 import com.intellij.openapi.components.service
+import com.intellij.openapi.options.SettingsEditor
 import com.intellij.openapi.util.SystemInfo
 import com.metalbear.mirrord.MirrordError
 import com.metalbear.mirrord.MirrordProjectService
+import com.metalbear.mirrord.MirrordRunConfigurationEditor
+import com.metalbear.mirrord.MirrordRunConfigurationOptions
+import org.jdom.Element
 import org.jetbrains.plugins.ruby.ruby.run.configuration.AbstractRubyRunConfiguration
 import org.jetbrains.plugins.ruby.ruby.run.configuration.RubyRunConfigurationExtension
 import kotlin.io.path.createTempFile
@@ -29,6 +33,22 @@ class RubyMineRunConfigurationExtension : RubyRunConfigurationExtension() {
         return true
     }
 
+    override fun <P : AbstractRubyRunConfiguration<*>> createEditor(configuration: P): SettingsEditor<P> {
+        return MirrordRunConfigurationEditor(configuration.project)
+    }
+
+    override fun getEditorTitle(): String {
+        return MirrordRunConfigurationEditor.TITLE
+    }
+
+    override fun readExternal(runConfiguration: AbstractRubyRunConfiguration<*>, element: Element) {
+        MirrordRunConfigurationOptions.readExternal(runConfiguration, element)
+    }
+
+    override fun writeExternal(runConfiguration: AbstractRubyRunConfiguration<*>, element: Element) {
+        MirrordRunConfigurationOptions.writeExternal(runConfiguration, element)
+    }
+
     override fun patchCommandLine(
         configuration: AbstractRubyRunConfiguration<*>,
         runnerSettings: RunnerSettings?,
@@ -47,6 +67,7 @@ class RubyMineRunConfigurationExtension : RubyRunConfigurationExtension() {
         val currentEnv = cmdLine.environment
         service.execManager.wrapper("rubymine", configuration.envs).apply {
             this.wsl = wsl
+            this.configuration = configuration
             if (isMac) {
                 this.executable = cmdLine.exePath
             }
@@ -3,6 +3,7 @@
 package com.metalbear.mirrord.products.tomcat
 
 import com.intellij.execution.ExecutionListener
+import com.intellij.execution.configurations.RunConfigurationBase
 import com.intellij.execution.process.ProcessHandler
 import com.intellij.execution.runners.ExecutionEnvironment
 import com.intellij.execution.target.createEnvironmentRequest
@@ -173,6 +174,7 @@ class TomcatExecutionListener : ExecutionListener {
         try {
             service.execManager.wrapper("tomcat", envVarsMap).apply {
                 this.wsl = wsl
+                this.configuration = env.runProfile as? RunConfigurationBase<*>
                 this.executable = scriptAndArgs?.command
             }.start()?.let { executionInfo ->
                 // `MIRRORD_IGNORE_DEBUGGER_PORTS` should allow clean shutdown of the app
@@ -0,0 +1,15 @@
+package com.metalbear.mirrord.products.tomcat
+
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.javaee.appServers.run.configuration.RunnerSpecificLocalConfigurationBit
+import com.metalbear.mirrord.MirrordRunConfigurationEnvProvider
+
+/**
+ * Tomcat keeps the environment in the runner specific settings, same as read by [TomcatExecutionListener].
+ */
+class TomcatRunConfigurationEnvProvider : MirrordRunConfigurationEnvProvider {
+    override fun env(environment: ExecutionEnvironment): Map<String, String>? {
+        val settings = environment.configurationSettings as? RunnerSpecificLocalConfigurationBit ?: return null
+        return settings.envVariables.associate { it.NAME to it.VALUE }
+    }
+}
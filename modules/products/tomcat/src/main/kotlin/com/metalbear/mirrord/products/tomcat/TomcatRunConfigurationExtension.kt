@@ -0,0 +1,45 @@
+package com.metalbear.mirrord.products.tomcat
+
+import com.intellij.execution.RunConfigurationExtension
+import com.intellij.execution.configurations.JavaParameters
+import com.intellij.execution.configurations.RunConfigurationBase
+import com.intellij.execution.configurations.RunnerSettings
+import com.intellij.javaee.appServers.run.configuration.CommonStrategy
+import com.intellij.openapi.options.SettingsEditor
+import com.metalbear.mirrord.MirrordRunConfigurationEditor
+import com.metalbear.mirrord.MirrordRunConfigurationOptions
+import org.jdom.Element
+
+/**
+ * Adds the mirrord tab to Tomcat run configurations. mirrord is started by [TomcatExecutionListener],
+ * this extension only edits and serializes the options.
+ *
+ * Applies to the run configurations skipped by the IDEA run configuration extension, which adds the tab to the others.
+ */
+class TomcatRunConfigurationExtension : RunConfigurationExtension() {
+    override fun isApplicableFor(configuration: RunConfigurationBase<*>): Boolean {
+        return configuration is CommonStrategy && configuration.name.startsWith("Tomcat")
+    }
+
+    override fun <P : RunConfigurationBase<*>> createEditor(configuration: P): SettingsEditor<P> {
+        return MirrordRunConfigurationEditor(configuration.project)
+    }
+
+    override fun getEditorTitle(): String {
+        return MirrordRunConfigurationEditor.TITLE
+    }
+
+    override fun readExternal(runConfiguration: RunConfigurationBase<*>, element: Element) {
+        MirrordRunConfigurationOptions.readExternal(runConfiguration, element)
+    }
+
+    override fun writeExternal(runConfiguration: RunConfigurationBase<*>, element: Element) {
+        MirrordRunConfigurationOptions.writeExternal(runConfiguration, element)
+    }
+
+    override fun <T : RunConfigurationBase<*>> updateJavaParameters(
+        configuration: T,
+        params: JavaParameters,
+        runnerSettings: RunnerSettings?
+    ) {}
+}
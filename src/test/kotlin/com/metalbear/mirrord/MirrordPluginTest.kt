@@ -206,6 +206,10 @@ internal class MirrordPluginTest {
                 step("Select pod to mirror traffic from") {
                     dialog("mirrord", ofSeconds(120)) {
                         val podToSelect = System.getenv("POD_TO_SELECT")
+                        // Targets are listed in the background, after the dialog is displayed.
+                        waitFor(ofSeconds(60)) {
+                            hasText(podToSelect)
+                        }
                         findText(podToSelect).click()
                         button("OK").click()
                     }